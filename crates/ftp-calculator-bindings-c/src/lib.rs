@@ -1,22 +1,130 @@
-use std::cell::RefCell;
-use std::ffi::c_char;
+//! C FFI layer.
+//!
+//! Builds `no_std` (plus `alloc`) when the default `std` feature is
+//! disabled, so this crate links into embedded/freestanding C hosts
+//! (bare-metal FFI, enclaves, WASM without a std shim). A `no_std` build
+//! still needs a global allocator (for `Box::into_raw`/`Box::from_raw`);
+//! the host binary must provide one via `#[global_allocator]`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(feature = "std")]
 use std::slice;
 
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use core::slice;
+
+use core::ffi::c_char;
+
 use ftp_calculator_core::{ComputeMethod, FtpResult};
 use ndarray::Array2;
 
-// Thread-local storage for the last error message.
+/// Fixed capacity of `FtpHandle::last_error`. Errors longer than this are
+/// truncated rather than allocated, so the error path itself never needs
+/// the allocator.
+const ERROR_BUF_LEN: usize = 256;
+
+// Thread-local storage for errors raised before a handle exists (e.g.
+// `ftp_create` rejecting malformed dimensions). Only available with the
+// `std` feature — embedded/no_std callers must pass well-formed arguments
+// to `ftp_create`, since there is no handle yet to carry the message.
+#[cfg(feature = "std")]
 thread_local! {
-    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+    static LAST_ERROR: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
 }
 
+#[cfg(feature = "std")]
 fn set_last_error(msg: String) {
     LAST_ERROR.with(|e| *e.borrow_mut() = msg);
 }
 
+/// Stable numeric result codes for the C FFI, so bindings can branch on the
+/// failure kind (`FTP_NOT_COMPUTED` vs `FTP_BUFFER_TOO_SMALL`, say) instead
+/// of scraping `ftp_handle_last_error`'s message text. The human-readable
+/// message path stays available alongside these for diagnostics.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FtpErrorCode {
+    Ok = 0,
+    NullArg = -1,
+    BadDims = -2,
+    ShapeMismatch = -3,
+    NotComputed = -4,
+    BufferTooSmall = -5,
+    UnknownMethod = -6,
+    ComputeFailed = -7,
+    /// A zero-copy `ftp_view_*` accessor was called on a non-contiguous array.
+    NotContiguous = -8,
+}
+
+/// Maps a core [`ftp_calculator_core::FtpError`] to its structured FFI code.
+fn error_code_for(e: &ftp_calculator_core::FtpError) -> FtpErrorCode {
+    use ftp_calculator_core::FtpError;
+    match e {
+        FtpError::DimensionMismatch { .. }
+        | FtpError::InvalidOutstandingColumns { .. }
+        | FtpError::RateProfileColumnMismatch { .. }
+        | FtpError::RowOutOfBounds { .. } => FtpErrorCode::BadDims,
+        FtpError::NotComputed => FtpErrorCode::NotComputed,
+        #[cfg(feature = "polars")]
+        FtpError::ColumnNotFound { .. } | FtpError::Io(_) => FtpErrorCode::ComputeFailed,
+        #[cfg(feature = "serde")]
+        FtpError::SerdeError(_) => FtpErrorCode::ComputeFailed,
+    }
+}
+
+/// Returns a static, NUL-terminated description of `code`, or `"unknown error code"`
+/// for anything not in [`FtpErrorCode`]. Never null.
+///
+/// # Safety
+/// The returned pointer is `'static` and must not be freed by the caller.
+#[no_mangle]
+pub unsafe extern "C" fn ftp_strerror(code: i32) -> *const c_char {
+    let msg: &str = match code {
+        x if x == FtpErrorCode::Ok as i32 => "ok\0",
+        x if x == FtpErrorCode::NullArg as i32 => "null pointer argument\0",
+        x if x == FtpErrorCode::BadDims as i32 => "invalid or inconsistent dimensions\0",
+        x if x == FtpErrorCode::ShapeMismatch as i32 => "input matrix shapes do not match\0",
+        x if x == FtpErrorCode::NotComputed as i32 => "result not yet computed\0",
+        x if x == FtpErrorCode::BufferTooSmall as i32 => "output buffer too small\0",
+        x if x == FtpErrorCode::UnknownMethod as i32 => "unknown compute method\0",
+        x if x == FtpErrorCode::ComputeFailed as i32 => "computation failed\0",
+        x if x == FtpErrorCode::NotContiguous as i32 => "array is not contiguous\0",
+        _ => "unknown error code\0",
+    };
+    msg.as_ptr() as *const c_char
+}
+
 /// Opaque handle returned to C callers.
+///
+/// Owns its own fixed-capacity error buffer rather than relying on a
+/// thread-local, so `ftp_handle_last_error` works even when the handle is
+/// created on one thread and its error read back on another.
 pub struct FtpHandle {
     inner: FtpResult,
+    last_error: [u8; ERROR_BUF_LEN],
+    last_error_len: usize,
+}
+
+impl FtpHandle {
+    fn new(inner: FtpResult) -> Self {
+        Self {
+            inner,
+            last_error: [0; ERROR_BUF_LEN],
+            last_error_len: 0,
+        }
+    }
+
+    fn set_last_error(&mut self, msg: &str) {
+        let bytes = msg.as_bytes();
+        let len = bytes.len().min(ERROR_BUF_LEN);
+        self.last_error[..len].copy_from_slice(&bytes[..len]);
+        self.last_error_len = len;
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -46,12 +154,14 @@ pub unsafe extern "C" fn ftp_create(
     rate_cols: i32,
 ) -> *mut FtpHandle {
     if outstanding.is_null() || profiles.is_null() || rates.is_null() {
+        #[cfg(feature = "std")]
         set_last_error("null pointer argument".into());
-        return std::ptr::null_mut();
+        return core::ptr::null_mut();
     }
     if outs_rows <= 0 || prof_rows <= 0 || prof_cols <= 0 || rate_rows <= 0 || rate_cols <= 0 {
+        #[cfg(feature = "std")]
         set_last_error("dimensions must be positive".into());
-        return std::ptr::null_mut();
+        return core::ptr::null_mut();
     }
 
     let outs_rows = outs_rows as usize;
@@ -66,28 +176,31 @@ pub unsafe extern "C" fn ftp_create(
 
     let input_outstanding = match Array2::from_shape_vec((outs_rows, 1), outs_slice.to_vec()) {
         Ok(a) => a,
-        Err(e) => {
-            set_last_error(format!("outstanding array: {}", e));
-            return std::ptr::null_mut();
+        Err(_e) => {
+            #[cfg(feature = "std")]
+            set_last_error(format!("outstanding array: {}", _e));
+            return core::ptr::null_mut();
         }
     };
     let input_profiles = match Array2::from_shape_vec((prof_rows, prof_cols), prof_slice.to_vec()) {
         Ok(a) => a,
-        Err(e) => {
-            set_last_error(format!("profiles array: {}", e));
-            return std::ptr::null_mut();
+        Err(_e) => {
+            #[cfg(feature = "std")]
+            set_last_error(format!("profiles array: {}", _e));
+            return core::ptr::null_mut();
         }
     };
     let input_rate = match Array2::from_shape_vec((rate_rows, rate_cols), rate_slice.to_vec()) {
         Ok(a) => a,
-        Err(e) => {
-            set_last_error(format!("rates array: {}", e));
-            return std::ptr::null_mut();
+        Err(_e) => {
+            #[cfg(feature = "std")]
+            set_last_error(format!("rates array: {}", _e));
+            return core::ptr::null_mut();
         }
     };
 
     let result = FtpResult::new(input_outstanding, input_profiles, input_rate);
-    let handle = Box::new(FtpHandle { inner: result });
+    let handle = Box::new(FtpHandle::new(result));
     Box::into_raw(handle)
 }
 
@@ -110,33 +223,40 @@ pub unsafe extern "C" fn ftp_free(handle: *mut FtpHandle) {
 ///
 /// - `method`: 0 = Stock, 1 = Flux
 ///
-/// Returns 0 on success, -1 on error (call `ftp_get_last_error`).
+/// Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success; call
+/// `ftp_handle_last_error` for the human-readable message, or
+/// `ftp_strerror` for a static description of the code).
 ///
 /// # Safety
 /// Caller must ensure the handle is valid and not null.
 #[no_mangle]
 pub unsafe extern "C" fn ftp_compute(handle: *mut FtpHandle, method: i32) -> i32 {
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
     if handle.is_null() {
+        #[cfg(feature = "std")]
         set_last_error("null handle".into());
-        return -1;
+        return FtpErrorCode::NullArg as i32;
     }
     let h = &mut *handle;
     let compute_method = match method {
         0 => ComputeMethod::Stock,
         1 => ComputeMethod::Flux,
         _ => {
-            set_last_error(format!(
+            h.set_last_error(&format!(
                 "unknown method: {} (expected 0=Stock, 1=Flux)",
                 method
             ));
-            return -1;
+            return FtpErrorCode::UnknownMethod as i32;
         }
     };
     match h.inner.compute(compute_method) {
-        Ok(()) => 0,
+        Ok(()) => FtpErrorCode::Ok as i32,
         Err(e) => {
-            set_last_error(e.to_string());
-            -1
+            let code = error_code_for(&e);
+            h.set_last_error(&format!("{}", e));
+            code as i32
         }
     }
 }
@@ -147,7 +267,7 @@ pub unsafe extern "C" fn ftp_compute(handle: *mut FtpHandle, method: i32) -> i32
 
 /// Writes the output matrix dimensions (rows, cols) into the provided pointers.
 ///
-/// Returns 0 on success, -1 on error.
+/// Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success).
 ///
 /// # Safety
 /// Caller must ensure all pointers are valid and not null.
@@ -158,210 +278,604 @@ pub unsafe extern "C" fn ftp_get_dims(
     out_cols: *mut i32,
 ) -> i32 {
     if handle.is_null() || out_rows.is_null() || out_cols.is_null() {
+        #[cfg(feature = "std")]
         set_last_error("null pointer argument".into());
-        return -1;
+        return FtpErrorCode::NullArg as i32;
     }
     let h = &*handle;
     let (r, c) = h.inner.input_profiles().dim();
     *out_rows = r as i32;
     *out_cols = c as i32;
-    0
+    FtpErrorCode::Ok as i32
 }
 
 // ---------------------------------------------------------------------------
 // Getters — copy matrix data into caller-provided buffer
 // ---------------------------------------------------------------------------
 
-/// Helper: copies an `Option<&Array2<f64>>` into a flat `out_buf` of length `buf_len`.
+/// Helper: copies an `Option<&Array2<f64>>` into a flat `out_buf` of length
+/// `buf_len`, recording any failure on `handle` (see [`FtpHandle::set_last_error`]).
 ///
-/// Returns 0 on success, -1 on error.
+/// Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success).
 unsafe fn copy_matrix(
+    handle: *mut FtpHandle,
     mat: Option<&Array2<f64>>,
     name: &str,
     out_buf: *mut f64,
     buf_len: i32,
 ) -> i32 {
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
     if out_buf.is_null() {
-        set_last_error("null output buffer".into());
-        return -1;
+        (*handle).set_last_error("null output buffer");
+        return FtpErrorCode::NullArg as i32;
     }
     let arr = match mat {
         Some(a) => a,
         None => {
-            set_last_error(format!("{}: not yet computed", name));
-            return -1;
+            (*handle).set_last_error(&format!("{}: not yet computed", name));
+            return FtpErrorCode::NotComputed as i32;
         }
     };
     let total = arr.len();
     if (buf_len as usize) < total {
-        set_last_error(format!(
+        (*handle).set_last_error(&format!(
             "{}: buffer too small ({} < {})",
             name, buf_len, total
         ));
-        return -1;
+        return FtpErrorCode::BufferTooSmall as i32;
     }
     // ndarray default layout is row-major — iterate in standard order
     let dst = slice::from_raw_parts_mut(out_buf, total);
     for (i, val) in arr.iter().enumerate() {
         dst[i] = *val;
     }
-    0
+    FtpErrorCode::Ok as i32
 }
 
-/// Copies stock_amort into `out_buf`. Returns 0 on success, -1 on error.
+/// Copies stock_amort into `out_buf`. Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success).
 ///
 /// # Safety
 /// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
 #[no_mangle]
 pub unsafe extern "C" fn ftp_get_stock_amort(
-    handle: *const FtpHandle,
+    handle: *mut FtpHandle,
+    out_buf: *mut f64,
+    buf_len: i32,
+) -> i32 {
+    ftp_get_output(handle, b"stock_amort\0".as_ptr() as *const c_char, out_buf, buf_len)
+}
+
+/// Copies stock_instal into `out_buf`. Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success).
+///
+/// # Safety
+/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+#[no_mangle]
+pub unsafe extern "C" fn ftp_get_stock_instal(
+    handle: *mut FtpHandle,
+    out_buf: *mut f64,
+    buf_len: i32,
+) -> i32 {
+    ftp_get_output(handle, b"stock_instal\0".as_ptr() as *const c_char, out_buf, buf_len)
+}
+
+/// Copies varstock_amort into `out_buf`. Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success).
+///
+/// # Safety
+/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+#[no_mangle]
+pub unsafe extern "C" fn ftp_get_varstock_amort(
+    handle: *mut FtpHandle,
+    out_buf: *mut f64,
+    buf_len: i32,
+) -> i32 {
+    ftp_get_output(handle, b"varstock_amort\0".as_ptr() as *const c_char, out_buf, buf_len)
+}
+
+/// Copies varstock_instal into `out_buf`. Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success).
+///
+/// # Safety
+/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+#[no_mangle]
+pub unsafe extern "C" fn ftp_get_varstock_instal(
+    handle: *mut FtpHandle,
+    out_buf: *mut f64,
+    buf_len: i32,
+) -> i32 {
+    ftp_get_output(handle, b"varstock_instal\0".as_ptr() as *const c_char, out_buf, buf_len)
+}
+
+/// Copies ftp_rate into `out_buf`. Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success).
+///
+/// # Safety
+/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+#[no_mangle]
+pub unsafe extern "C" fn ftp_get_ftp_rate(
+    handle: *mut FtpHandle,
+    out_buf: *mut f64,
+    buf_len: i32,
+) -> i32 {
+    ftp_get_output(handle, b"ftp_rate\0".as_ptr() as *const c_char, out_buf, buf_len)
+}
+
+/// Copies ftp_int into `out_buf`. Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success).
+///
+/// # Safety
+/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+#[no_mangle]
+pub unsafe extern "C" fn ftp_get_ftp_int(
+    handle: *mut FtpHandle,
+    out_buf: *mut f64,
+    buf_len: i32,
+) -> i32 {
+    ftp_get_output(handle, b"ftp_int\0".as_ptr() as *const c_char, out_buf, buf_len)
+}
+
+/// Copies market_rate into `out_buf`. Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success).
+///
+/// # Safety
+/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+#[no_mangle]
+pub unsafe extern "C" fn ftp_get_market_rate(
+    handle: *mut FtpHandle,
     out_buf: *mut f64,
     buf_len: i32,
+) -> i32 {
+    ftp_get_output(handle, b"market_rate\0".as_ptr() as *const c_char, out_buf, buf_len)
+}
+
+// ---------------------------------------------------------------------------
+// Zero-copy views — borrow the handle's own Array2 instead of copying
+// ---------------------------------------------------------------------------
+
+/// Helper: hands back a const pointer directly into `mat`'s contiguous
+/// storage, plus its dimensions, instead of copying into a caller buffer.
+/// `ndarray`'s default layout is contiguous row-major, so this only fails
+/// when the array has been sliced/transposed into a non-contiguous view
+/// (not possible for `FtpResult`'s own outputs today, but kept as a
+/// guard rather than an `unwrap`).
+///
+/// The returned pointer is valid until `ftp_free` or the next
+/// `ftp_compute` on the same handle.
+///
+/// Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success).
+unsafe fn view_matrix(
+    handle: *mut FtpHandle,
+    mat: Option<&Array2<f64>>,
+    name: &str,
+    out_ptr: *mut *const f64,
+    out_rows: *mut i32,
+    out_cols: *mut i32,
+) -> i32 {
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    if out_ptr.is_null() || out_rows.is_null() || out_cols.is_null() {
+        (*handle).set_last_error("null output pointer");
+        return FtpErrorCode::NullArg as i32;
+    }
+    let arr = match mat {
+        Some(a) => a,
+        None => {
+            (*handle).set_last_error(&format!("{}: not yet computed", name));
+            return FtpErrorCode::NotComputed as i32;
+        }
+    };
+    let slice = match arr.as_slice() {
+        Some(s) => s,
+        None => {
+            (*handle).set_last_error(&format!("{}: not contiguous", name));
+            return FtpErrorCode::NotContiguous as i32;
+        }
+    };
+    let (r, c) = arr.dim();
+    *out_ptr = slice.as_ptr();
+    *out_rows = r as i32;
+    *out_cols = c as i32;
+    FtpErrorCode::Ok as i32
+}
+
+/// Borrows stock_amort without copying. `*out_ptr` points at `out_rows *
+/// out_cols` row-major doubles owned by `handle`, valid until `ftp_free`
+/// or the next `ftp_compute` on `handle`.
+///
+/// Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success,
+/// `FTP_NOT_CONTIGUOUS` if the array cannot be borrowed — fall back to
+/// `ftp_get_stock_amort` in that case).
+///
+/// # Safety
+/// Caller must ensure handle and all out-pointers are valid, and must not
+/// read through `*out_ptr` after `ftp_free`/`ftp_compute` invalidates it.
+#[no_mangle]
+pub unsafe extern "C" fn ftp_view_stock_amort(
+    handle: *mut FtpHandle,
+    out_ptr: *mut *const f64,
+    out_rows: *mut i32,
+    out_cols: *mut i32,
 ) -> i32 {
     if handle.is_null() {
+        #[cfg(feature = "std")]
         set_last_error("null handle".into());
-        return -1;
+        return FtpErrorCode::NullArg as i32;
     }
-    copy_matrix(
+    view_matrix(
+        handle,
         (*handle).inner.stock_amort(),
         "stock_amort",
-        out_buf,
-        buf_len,
+        out_ptr,
+        out_rows,
+        out_cols,
     )
 }
 
-/// Copies stock_instal into `out_buf`. Returns 0 on success, -1 on error.
+/// Borrows stock_instal without copying. See [`ftp_view_stock_amort`] for
+/// pointer lifetime and error semantics.
 ///
 /// # Safety
-/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+/// Caller must ensure handle and all out-pointers are valid, and must not
+/// read through `*out_ptr` after `ftp_free`/`ftp_compute` invalidates it.
 #[no_mangle]
-pub unsafe extern "C" fn ftp_get_stock_instal(
-    handle: *const FtpHandle,
-    out_buf: *mut f64,
-    buf_len: i32,
+pub unsafe extern "C" fn ftp_view_stock_instal(
+    handle: *mut FtpHandle,
+    out_ptr: *mut *const f64,
+    out_rows: *mut i32,
+    out_cols: *mut i32,
 ) -> i32 {
     if handle.is_null() {
+        #[cfg(feature = "std")]
         set_last_error("null handle".into());
-        return -1;
+        return FtpErrorCode::NullArg as i32;
     }
-    copy_matrix(
+    view_matrix(
+        handle,
         (*handle).inner.stock_instal(),
         "stock_instal",
-        out_buf,
-        buf_len,
+        out_ptr,
+        out_rows,
+        out_cols,
     )
 }
 
-/// Copies varstock_amort into `out_buf`. Returns 0 on success, -1 on error.
+/// Borrows varstock_amort without copying. See [`ftp_view_stock_amort`] for
+/// pointer lifetime and error semantics.
 ///
 /// # Safety
-/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+/// Caller must ensure handle and all out-pointers are valid, and must not
+/// read through `*out_ptr` after `ftp_free`/`ftp_compute` invalidates it.
 #[no_mangle]
-pub unsafe extern "C" fn ftp_get_varstock_amort(
-    handle: *const FtpHandle,
-    out_buf: *mut f64,
-    buf_len: i32,
+pub unsafe extern "C" fn ftp_view_varstock_amort(
+    handle: *mut FtpHandle,
+    out_ptr: *mut *const f64,
+    out_rows: *mut i32,
+    out_cols: *mut i32,
 ) -> i32 {
     if handle.is_null() {
+        #[cfg(feature = "std")]
         set_last_error("null handle".into());
-        return -1;
+        return FtpErrorCode::NullArg as i32;
     }
-    copy_matrix(
+    view_matrix(
+        handle,
         (*handle).inner.varstock_amort(),
         "varstock_amort",
-        out_buf,
-        buf_len,
+        out_ptr,
+        out_rows,
+        out_cols,
     )
 }
 
-/// Copies varstock_instal into `out_buf`. Returns 0 on success, -1 on error.
+/// Borrows varstock_instal without copying. See [`ftp_view_stock_amort`] for
+/// pointer lifetime and error semantics.
 ///
 /// # Safety
-/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+/// Caller must ensure handle and all out-pointers are valid, and must not
+/// read through `*out_ptr` after `ftp_free`/`ftp_compute` invalidates it.
 #[no_mangle]
-pub unsafe extern "C" fn ftp_get_varstock_instal(
-    handle: *const FtpHandle,
-    out_buf: *mut f64,
-    buf_len: i32,
+pub unsafe extern "C" fn ftp_view_varstock_instal(
+    handle: *mut FtpHandle,
+    out_ptr: *mut *const f64,
+    out_rows: *mut i32,
+    out_cols: *mut i32,
 ) -> i32 {
     if handle.is_null() {
+        #[cfg(feature = "std")]
         set_last_error("null handle".into());
-        return -1;
+        return FtpErrorCode::NullArg as i32;
     }
-    copy_matrix(
+    view_matrix(
+        handle,
         (*handle).inner.varstock_instal(),
         "varstock_instal",
-        out_buf,
-        buf_len,
+        out_ptr,
+        out_rows,
+        out_cols,
     )
 }
 
-/// Copies ftp_rate into `out_buf`. Returns 0 on success, -1 on error.
+/// Borrows ftp_rate without copying. See [`ftp_view_stock_amort`] for
+/// pointer lifetime and error semantics.
 ///
 /// # Safety
-/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+/// Caller must ensure handle and all out-pointers are valid, and must not
+/// read through `*out_ptr` after `ftp_free`/`ftp_compute` invalidates it.
 #[no_mangle]
-pub unsafe extern "C" fn ftp_get_ftp_rate(
-    handle: *const FtpHandle,
-    out_buf: *mut f64,
-    buf_len: i32,
+pub unsafe extern "C" fn ftp_view_ftp_rate(
+    handle: *mut FtpHandle,
+    out_ptr: *mut *const f64,
+    out_rows: *mut i32,
+    out_cols: *mut i32,
 ) -> i32 {
     if handle.is_null() {
+        #[cfg(feature = "std")]
         set_last_error("null handle".into());
-        return -1;
+        return FtpErrorCode::NullArg as i32;
     }
-    copy_matrix((*handle).inner.ftp_rate(), "ftp_rate", out_buf, buf_len)
+    view_matrix(
+        handle,
+        (*handle).inner.ftp_rate(),
+        "ftp_rate",
+        out_ptr,
+        out_rows,
+        out_cols,
+    )
 }
 
-/// Copies ftp_int into `out_buf`. Returns 0 on success, -1 on error.
+/// Borrows ftp_int without copying. See [`ftp_view_stock_amort`] for
+/// pointer lifetime and error semantics.
 ///
 /// # Safety
-/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+/// Caller must ensure handle and all out-pointers are valid, and must not
+/// read through `*out_ptr` after `ftp_free`/`ftp_compute` invalidates it.
 #[no_mangle]
-pub unsafe extern "C" fn ftp_get_ftp_int(
-    handle: *const FtpHandle,
-    out_buf: *mut f64,
-    buf_len: i32,
+pub unsafe extern "C" fn ftp_view_ftp_int(
+    handle: *mut FtpHandle,
+    out_ptr: *mut *const f64,
+    out_rows: *mut i32,
+    out_cols: *mut i32,
 ) -> i32 {
     if handle.is_null() {
+        #[cfg(feature = "std")]
         set_last_error("null handle".into());
-        return -1;
+        return FtpErrorCode::NullArg as i32;
     }
-    copy_matrix((*handle).inner.ftp_int(), "ftp_int", out_buf, buf_len)
+    view_matrix(
+        handle,
+        (*handle).inner.ftp_int(),
+        "ftp_int",
+        out_ptr,
+        out_rows,
+        out_cols,
+    )
 }
 
-/// Copies market_rate into `out_buf`. Returns 0 on success, -1 on error.
+/// Borrows market_rate without copying. See [`ftp_view_stock_amort`] for
+/// pointer lifetime and error semantics.
 ///
 /// # Safety
-/// Caller must ensure handle and out_buf are valid pointers with sufficient capacity.
+/// Caller must ensure handle and all out-pointers are valid, and must not
+/// read through `*out_ptr` after `ftp_free`/`ftp_compute` invalidates it.
 #[no_mangle]
-pub unsafe extern "C" fn ftp_get_market_rate(
-    handle: *const FtpHandle,
-    out_buf: *mut f64,
-    buf_len: i32,
+pub unsafe extern "C" fn ftp_view_market_rate(
+    handle: *mut FtpHandle,
+    out_ptr: *mut *const f64,
+    out_rows: *mut i32,
+    out_cols: *mut i32,
 ) -> i32 {
     if handle.is_null() {
+        #[cfg(feature = "std")]
         set_last_error("null handle".into());
-        return -1;
+        return FtpErrorCode::NullArg as i32;
     }
-    copy_matrix(
+    view_matrix(
+        handle,
         (*handle).inner.market_rate(),
         "market_rate",
-        out_buf,
-        buf_len,
+        out_ptr,
+        out_rows,
+        out_cols,
     )
 }
 
+// ---------------------------------------------------------------------------
+// Generic name-keyed dispatch — the typed `ftp_get_*` getters are thin
+// wrappers over this, so adding a new `FtpResult` output only means adding
+// one arm here rather than a new exported symbol.
+// ---------------------------------------------------------------------------
+
+/// Output names recognized by `ftp_get_output`/`ftp_list_outputs`, in a
+/// fixed order shared between `OUTPUT_NAMES` (for name matching) and
+/// `OUTPUT_NAMES_NUL` (for the static NUL-terminated pointers
+/// `ftp_list_outputs` hands back).
+const OUTPUT_NAMES: &[&str] = &[
+    "stock_amort",
+    "stock_instal",
+    "varstock_amort",
+    "varstock_instal",
+    "ftp_rate",
+    "ftp_int",
+    "market_rate",
+];
+
+const OUTPUT_NAMES_NUL: &[&str] = &[
+    "stock_amort\0",
+    "stock_instal\0",
+    "varstock_amort\0",
+    "varstock_instal\0",
+    "ftp_rate\0",
+    "ftp_int\0",
+    "market_rate\0",
+];
+
+fn output_at(inner: &FtpResult, idx: usize) -> Option<&Array2<f64>> {
+    match idx {
+        0 => inner.stock_amort(),
+        1 => inner.stock_instal(),
+        2 => inner.varstock_amort(),
+        3 => inner.varstock_instal(),
+        4 => inner.ftp_rate(),
+        5 => inner.ftp_int(),
+        6 => inner.market_rate(),
+        _ => None,
+    }
+}
+
+/// Copies the named output (`"stock_amort"`, `"ftp_rate"`, ...) into
+/// `out_buf`, the same way the typed `ftp_get_*` getters do — see
+/// `OUTPUT_NAMES` for the recognized names.
+///
+/// Returns an [`FtpErrorCode`] discriminant (`FTP_OK` on success,
+/// `FTP_NOT_COMPUTED` for an unrecognized name or an output not yet
+/// computed).
+///
+/// # Safety
+/// Caller must ensure handle, name, and out_buf are valid pointers, with
+/// name NUL-terminated and out_buf having sufficient capacity.
+#[no_mangle]
+pub unsafe extern "C" fn ftp_get_output(
+    handle: *mut FtpHandle,
+    name: *const c_char,
+    out_buf: *mut f64,
+    buf_len: i32,
+) -> i32 {
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    if handle.is_null() {
+        #[cfg(feature = "std")]
+        set_last_error("null handle".into());
+        return FtpErrorCode::NullArg as i32;
+    }
+    if name.is_null() {
+        (*handle).set_last_error("null output name");
+        return FtpErrorCode::NullArg as i32;
+    }
+    let name = match core::ffi::CStr::from_ptr(name).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            (*handle).set_last_error("output name is not valid UTF-8");
+            return FtpErrorCode::NullArg as i32;
+        }
+    };
+    match OUTPUT_NAMES.iter().position(|&n| n == name) {
+        Some(idx) => copy_matrix(
+            handle,
+            output_at(&(*handle).inner, idx),
+            name,
+            out_buf,
+            buf_len,
+        ),
+        None => {
+            (*handle).set_last_error(&format!("unknown output name: {}", name));
+            FtpErrorCode::NotComputed as i32
+        }
+    }
+}
+
+/// Writes up to `max` static, NUL-terminated output names into `out_names`
+/// for every output currently populated on `handle` (i.e. computed by a
+/// prior `ftp_compute`), in `OUTPUT_NAMES` order. Lets dynamic bindings
+/// (Python/ctypes, R, Julia) discover available fields at runtime instead
+/// of hardcoding symbol names.
+///
+/// Returns the number of names written on success, or an [`FtpErrorCode`]
+/// discriminant (negative) on error — in particular `FTP_BUFFER_TOO_SMALL`
+/// if more outputs are populated than `max` can hold.
+///
+/// # Safety
+/// Caller must ensure handle and out_names are valid, with out_names
+/// pointing to room for at least `max` pointers.
+#[no_mangle]
+pub unsafe extern "C" fn ftp_list_outputs(
+    handle: *mut FtpHandle,
+    out_names: *mut *const c_char,
+    max: i32,
+) -> i32 {
+    if handle.is_null() {
+        #[cfg(feature = "std")]
+        set_last_error("null handle".into());
+        return FtpErrorCode::NullArg as i32;
+    }
+    if out_names.is_null() || max < 0 {
+        (*handle).set_last_error("null output name buffer");
+        return FtpErrorCode::NullArg as i32;
+    }
+
+    let inner = &(*handle).inner;
+    let mut populated = [0usize; OUTPUT_NAMES.len()];
+    let mut count = 0usize;
+    for idx in 0..OUTPUT_NAMES.len() {
+        if output_at(inner, idx).is_some() {
+            populated[count] = idx;
+            count += 1;
+        }
+    }
+
+    if count > max as usize {
+        #[cfg(feature = "std")]
+        (*handle).set_last_error(&format!("{} outputs populated, buffer holds {}", count, max));
+        #[cfg(not(feature = "std"))]
+        (*handle).set_last_error("more outputs populated than the buffer holds");
+        return FtpErrorCode::BufferTooSmall as i32;
+    }
+
+    let dst = slice::from_raw_parts_mut(out_names, count);
+    for (slot, idx) in dst.iter_mut().zip(populated[..count].iter()) {
+        *slot = OUTPUT_NAMES_NUL[*idx].as_ptr() as *const c_char;
+    }
+    count as i32
+}
+
 // ---------------------------------------------------------------------------
 // Error reporting
 // ---------------------------------------------------------------------------
 
-/// Copies the last error message into `buf` (max `buf_len` bytes, NUL-terminated).
+/// Copies the error message recorded on `handle` into `buf` (max `buf_len`
+/// bytes, NUL-terminated). Covers any error from `ftp_compute` or an
+/// `ftp_get_*` getter on this handle — unlike the old thread-local error
+/// slot, this works even if the handle is read back from a different
+/// thread than the one that created or computed it.
+///
+/// Returns 0 on success, -1 if `handle`/`buf` is null or the message was
+/// truncated.
+///
+/// # Safety
+/// Caller must ensure handle and buf are valid pointers, with buf pointing
+/// to a buffer of at least buf_len bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ftp_handle_last_error(
+    handle: *const FtpHandle,
+    buf: *mut c_char,
+    buf_len: i32,
+) -> i32 {
+    if handle.is_null() || buf.is_null() || buf_len <= 0 {
+        return -1;
+    }
+    let h = &*handle;
+    let bytes = &h.last_error[..h.last_error_len];
+    let max = (buf_len as usize) - 1; // leave room for NUL
+    let copy_len = bytes.len().min(max);
+    let dst = slice::from_raw_parts_mut(buf as *mut u8, buf_len as usize);
+    dst[..copy_len].copy_from_slice(&bytes[..copy_len]);
+    dst[copy_len] = 0; // NUL terminator
+    if bytes.len() > max {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Copies the last *pre-handle* error message (e.g. `ftp_create` rejecting
+/// malformed dimensions, before any handle exists to carry the message)
+/// into `buf` (max `buf_len` bytes, NUL-terminated). Only available with
+/// the `std` feature, and shared across handles on the calling thread —
+/// for any error tied to a specific handle, use `ftp_handle_last_error`.
 ///
 /// Returns 0 on success, -1 if `buf` is null or the message was truncated.
 ///
 /// # Safety
 /// Caller must ensure buf is a valid pointer to a buffer of at least buf_len bytes.
+#[cfg(feature = "std")]
 #[no_mangle]
 pub unsafe extern "C" fn ftp_get_last_error(buf: *mut c_char, buf_len: i32) -> i32 {
     if buf.is_null() || buf_len <= 0 {
@@ -498,17 +1012,20 @@ mod tests {
     #[test]
     fn test_null_handle_returns_error() {
         unsafe {
-            assert_eq!(ftp_compute(std::ptr::null_mut(), 0), -1);
+            assert_eq!(ftp_compute(std::ptr::null_mut(), 0), FtpErrorCode::NullArg as i32);
 
             let mut buf = vec![0.0f64; 10];
             assert_eq!(
-                ftp_get_stock_amort(std::ptr::null(), buf.as_mut_ptr(), 10),
+                ftp_get_stock_amort(std::ptr::null_mut(), buf.as_mut_ptr(), 10),
                 -1
             );
 
             let mut rows: i32 = 0;
             let mut cols: i32 = 0;
-            assert_eq!(ftp_get_dims(std::ptr::null(), &mut rows, &mut cols), -1);
+            assert_eq!(
+                ftp_get_dims(std::ptr::null(), &mut rows, &mut cols),
+                FtpErrorCode::NullArg as i32
+            );
         }
     }
 
@@ -530,11 +1047,11 @@ mod tests {
                 1,
             );
             assert!(!h.is_null());
-            assert_eq!(ftp_compute(h, 99), -1);
+            assert_eq!(ftp_compute(h, 99), FtpErrorCode::UnknownMethod as i32);
 
-            // Read the error
+            // Read the error back off the handle itself.
             let mut err_buf = vec![0i8; 256];
-            ftp_get_last_error(err_buf.as_mut_ptr(), 256);
+            ftp_handle_last_error(h, err_buf.as_mut_ptr(), 256);
             let msg = std::ffi::CStr::from_ptr(err_buf.as_ptr()).to_string_lossy();
             assert!(msg.contains("unknown method"));
 
@@ -563,7 +1080,10 @@ mod tests {
 
             // Buffer of 1 when we need 2
             let mut buf = vec![0.0f64; 1];
-            assert_eq!(ftp_get_stock_amort(h, buf.as_mut_ptr(), 1), -1);
+            assert_eq!(
+                ftp_get_stock_amort(h, buf.as_mut_ptr(), 1),
+                FtpErrorCode::BufferTooSmall as i32
+            );
 
             ftp_free(h);
         }
@@ -588,7 +1108,254 @@ mod tests {
             );
 
             let mut buf = vec![0.0f64; 2];
-            assert_eq!(ftp_get_stock_amort(h, buf.as_mut_ptr(), 2), -1);
+            assert_eq!(
+                ftp_get_stock_amort(h, buf.as_mut_ptr(), 2),
+                FtpErrorCode::NotComputed as i32
+            );
+
+            ftp_free(h);
+        }
+    }
+
+    #[test]
+    fn test_strerror_covers_every_code() {
+        unsafe {
+            let codes = [
+                FtpErrorCode::Ok,
+                FtpErrorCode::NullArg,
+                FtpErrorCode::BadDims,
+                FtpErrorCode::ShapeMismatch,
+                FtpErrorCode::NotComputed,
+                FtpErrorCode::BufferTooSmall,
+                FtpErrorCode::UnknownMethod,
+                FtpErrorCode::ComputeFailed,
+                FtpErrorCode::NotContiguous,
+            ];
+            for code in codes {
+                let msg = std::ffi::CStr::from_ptr(ftp_strerror(code as i32)).to_string_lossy();
+                assert!(!msg.is_empty());
+                assert_ne!(msg, "unknown error code");
+            }
+            let msg = std::ffi::CStr::from_ptr(ftp_strerror(-99)).to_string_lossy();
+            assert_eq!(msg, "unknown error code");
+        }
+    }
+
+    #[test]
+    fn test_view_stock_amort_matches_copy_path() {
+        unsafe {
+            let outstanding = [1000.0f64, 1200.0, 1350.0];
+            let profiles = [
+                1.00, 0.50, 0.20, 0.05, // row 0
+                1.00, 0.50, 0.20, 0.05, // row 1
+                1.00, 0.50, 0.20, 0.05, // row 2
+            ];
+            let rates = [
+                0.01300, 0.01400, 0.01600, // row 0
+                0.01360, 0.01460, 0.01660, // row 1
+                0.01430, 0.01530, 0.01730, // row 2
+            ];
+
+            let h = ftp_create(
+                outstanding.as_ptr(),
+                3,
+                profiles.as_ptr(),
+                3,
+                4,
+                rates.as_ptr(),
+                3,
+                3,
+            );
+            assert_eq!(ftp_compute(h, 0), FtpErrorCode::Ok as i32);
+
+            let mut view_ptr: *const f64 = std::ptr::null();
+            let mut view_rows: i32 = 0;
+            let mut view_cols: i32 = 0;
+            assert_eq!(
+                ftp_view_stock_amort(h, &mut view_ptr, &mut view_rows, &mut view_cols),
+                FtpErrorCode::Ok as i32
+            );
+            assert_eq!(view_rows, 3);
+            assert_eq!(view_cols, 4);
+
+            let viewed = slice::from_raw_parts(view_ptr, (view_rows * view_cols) as usize);
+
+            let mut copied = vec![0.0f64; (view_rows * view_cols) as usize];
+            assert_eq!(
+                ftp_get_stock_amort(h, copied.as_mut_ptr(), copied.len() as i32),
+                FtpErrorCode::Ok as i32
+            );
+
+            assert_eq!(viewed, &copied[..]);
+
+            ftp_free(h);
+        }
+    }
+
+    #[test]
+    fn test_view_before_compute_returns_not_computed() {
+        unsafe {
+            let outstanding = [1000.0f64];
+            let profiles = [1.00, 0.50];
+            let rates = [0.01];
+
+            let h = ftp_create(
+                outstanding.as_ptr(),
+                1,
+                profiles.as_ptr(),
+                1,
+                2,
+                rates.as_ptr(),
+                1,
+                1,
+            );
+
+            let mut view_ptr: *const f64 = std::ptr::null();
+            let mut view_rows: i32 = 0;
+            let mut view_cols: i32 = 0;
+            assert_eq!(
+                ftp_view_stock_amort(h, &mut view_ptr, &mut view_rows, &mut view_cols),
+                FtpErrorCode::NotComputed as i32
+            );
+
+            ftp_free(h);
+        }
+    }
+
+    #[test]
+    fn test_get_output_matches_typed_getter() {
+        unsafe {
+            let outstanding = [1000.0f64, 1200.0, 1350.0];
+            let profiles = [
+                1.00, 0.50, 0.20, 0.05, // row 0
+                1.00, 0.50, 0.20, 0.05, // row 1
+                1.00, 0.50, 0.20, 0.05, // row 2
+            ];
+            let rates = [
+                0.01300, 0.01400, 0.01600, // row 0
+                0.01360, 0.01460, 0.01660, // row 1
+                0.01430, 0.01530, 0.01730, // row 2
+            ];
+
+            let h = ftp_create(
+                outstanding.as_ptr(),
+                3,
+                profiles.as_ptr(),
+                3,
+                4,
+                rates.as_ptr(),
+                3,
+                3,
+            );
+            assert_eq!(ftp_compute(h, 0), FtpErrorCode::Ok as i32);
+
+            let mut via_dispatch = [0.0f64; 12];
+            let mut via_typed = [0.0f64; 12];
+            let name = std::ffi::CString::new("stock_amort").unwrap();
+            assert_eq!(
+                ftp_get_output(h, name.as_ptr(), via_dispatch.as_mut_ptr(), 12),
+                FtpErrorCode::Ok as i32
+            );
+            assert_eq!(
+                ftp_get_stock_amort(h, via_typed.as_mut_ptr(), 12),
+                FtpErrorCode::Ok as i32
+            );
+            assert_eq!(via_dispatch, via_typed);
+
+            ftp_free(h);
+        }
+    }
+
+    #[test]
+    fn test_get_output_unknown_name_returns_not_computed() {
+        unsafe {
+            let outstanding = [1000.0f64];
+            let profiles = [1.00, 0.50];
+            let rates = [0.01];
+
+            let h = ftp_create(
+                outstanding.as_ptr(),
+                1,
+                profiles.as_ptr(),
+                1,
+                2,
+                rates.as_ptr(),
+                1,
+                1,
+            );
+            assert_eq!(ftp_compute(h, 0), FtpErrorCode::Ok as i32);
+
+            let mut buf = [0.0f64; 4];
+            let name = std::ffi::CString::new("not_a_real_output").unwrap();
+            assert_eq!(
+                ftp_get_output(h, name.as_ptr(), buf.as_mut_ptr(), 4),
+                FtpErrorCode::NotComputed as i32
+            );
+
+            ftp_free(h);
+        }
+    }
+
+    #[test]
+    fn test_list_outputs_reports_populated_after_compute() {
+        unsafe {
+            let outstanding = [1000.0f64];
+            let profiles = [1.00, 0.50];
+            let rates = [0.01];
+
+            let h = ftp_create(
+                outstanding.as_ptr(),
+                1,
+                profiles.as_ptr(),
+                1,
+                2,
+                rates.as_ptr(),
+                1,
+                1,
+            );
+
+            let mut names: [*const c_char; 7] = [std::ptr::null(); 7];
+            let before = ftp_list_outputs(h, names.as_mut_ptr(), 7);
+            assert_eq!(before, 0);
+
+            assert_eq!(ftp_compute(h, 0), FtpErrorCode::Ok as i32);
+
+            let after = ftp_list_outputs(h, names.as_mut_ptr(), 7);
+            assert!(after > 0);
+            let reported: Vec<String> = names[..after as usize]
+                .iter()
+                .map(|&p| std::ffi::CStr::from_ptr(p).to_string_lossy().into_owned())
+                .collect();
+            assert!(reported.contains(&"stock_amort".to_string()));
+
+            ftp_free(h);
+        }
+    }
+
+    #[test]
+    fn test_list_outputs_buffer_too_small() {
+        unsafe {
+            let outstanding = [1000.0f64];
+            let profiles = [1.00, 0.50];
+            let rates = [0.01];
+
+            let h = ftp_create(
+                outstanding.as_ptr(),
+                1,
+                profiles.as_ptr(),
+                1,
+                2,
+                rates.as_ptr(),
+                1,
+                1,
+            );
+            assert_eq!(ftp_compute(h, 0), FtpErrorCode::Ok as i32);
+
+            let mut names: [*const c_char; 1] = [std::ptr::null(); 1];
+            assert_eq!(
+                ftp_list_outputs(h, names.as_mut_ptr(), 1),
+                FtpErrorCode::BufferTooSmall as i32
+            );
 
             ftp_free(h);
         }
@@ -1,5 +1,5 @@
-use ndarray::Array2;
-use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+use ndarray::{Array2, Array3, ArrayD, Axis, Ix2};
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2, PyReadonlyArrayDyn, PyReadwriteArray2};
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
@@ -19,6 +19,34 @@ fn require_output<'a>(opt: Option<&'a Array2<f64>>, name: &str) -> PyResult<&'a
     })
 }
 
+/// Parses the `"stock"`/`"flux"` method string shared by `compute` and
+/// `compute_into`.
+fn parse_method(method: &str) -> PyResult<ComputeMethod> {
+    match method {
+        "stock" => Ok(ComputeMethod::Stock),
+        "flux" => Ok(ComputeMethod::Flux),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unknown method '{other}' — use 'stock' or 'flux'"
+        ))),
+    }
+}
+
+/// Writes `src` into `dst`, a caller-provided numpy buffer, instead of
+/// allocating a fresh array — rejects `dst` with a ValueError if its shape
+/// doesn't already match `src`.
+fn write_into(dst: &mut PyReadwriteArray2<'_, f64>, src: &Array2<f64>, name: &str) -> PyResult<()> {
+    let mut view = dst.as_array_mut();
+    if view.dim() != src.dim() {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "'{name}' has shape {:?}, expected {:?}",
+            view.dim(),
+            src.dim()
+        )));
+    }
+    view.assign(src);
+    Ok(())
+}
+
 /// FTP Calculator — wraps the Rust ftp_core engine.
 ///
 /// Usage:
@@ -32,6 +60,7 @@ struct FtpCalculator {
 
 #[pymethods]
 impl FtpCalculator {
+    #[cfg(not(feature = "serde"))]
     #[new]
     fn new(
         outstanding: PyReadonlyArray2<'_, f64>,
@@ -47,18 +76,112 @@ impl FtpCalculator {
         }
     }
 
+    /// All three arguments are optional so unpickling (via `__setstate__`)
+    /// can build a placeholder instance before the real state is restored.
+    #[cfg(feature = "serde")]
+    #[new]
+    #[pyo3(signature = (outstanding=None, profiles=None, rates=None))]
+    fn new(
+        outstanding: Option<PyReadonlyArray2<'_, f64>>,
+        profiles: Option<PyReadonlyArray2<'_, f64>>,
+        rates: Option<PyReadonlyArray2<'_, f64>>,
+    ) -> PyResult<Self> {
+        match (outstanding, profiles, rates) {
+            (Some(o), Some(p), Some(r)) => Ok(Self {
+                inner: FtpResult::new(
+                    o.as_array().to_owned(),
+                    p.as_array().to_owned(),
+                    r.as_array().to_owned(),
+                ),
+            }),
+            (None, None, None) => Ok(Self {
+                inner: FtpResult::new(
+                    Array2::zeros((0, 1)),
+                    Array2::zeros((0, 0)),
+                    Array2::zeros((0, 0)),
+                ),
+            }),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(
+                "outstanding, profiles, and rates must be given together",
+            )),
+        }
+    }
+
+    /// Serializes inputs and computed outputs (if any) to JSON bytes for
+    /// Python's pickle protocol.
+    #[cfg(feature = "serde")]
+    fn __getstate__(&self) -> PyResult<Vec<u8>> {
+        self.inner.to_json().map(|s| s.into_bytes()).map_err(ftp_err)
+    }
+
+    /// Restores state previously produced by `__getstate__`.
+    #[cfg(feature = "serde")]
+    fn __setstate__(&mut self, state: Vec<u8>) -> PyResult<()> {
+        let json = String::from_utf8(state)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        self.inner = FtpResult::from_json(&json).map_err(ftp_err)?;
+        Ok(())
+    }
+
     /// Run the FTP computation. method must be "stock" or "flux".
     fn compute(&mut self, method: &str) -> PyResult<()> {
-        let m = match method {
-            "stock" => ComputeMethod::Stock,
-            "flux" => ComputeMethod::Flux,
-            other => {
-                return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                    "unknown method '{other}' — use 'stock' or 'flux'"
-                )));
-            }
-        };
-        self.inner.compute(m).map_err(ftp_err)
+        self.inner.compute(parse_method(method)?).map_err(ftp_err)
+    }
+
+    /// Runs `compute(method)` and writes each output directly into the
+    /// caller-provided, already-shaped `(nrows, ncols)` numpy buffers
+    /// instead of allocating fresh arrays — lets callers reuse the same
+    /// buffers across many portfolio runs.
+    #[allow(clippy::too_many_arguments)]
+    fn compute_into(
+        &mut self,
+        method: &str,
+        mut stock_amort: PyReadwriteArray2<'_, f64>,
+        mut stock_instal: PyReadwriteArray2<'_, f64>,
+        mut varstock_amort: PyReadwriteArray2<'_, f64>,
+        mut varstock_instal: PyReadwriteArray2<'_, f64>,
+        mut ftp_rate: PyReadwriteArray2<'_, f64>,
+        mut ftp_int: PyReadwriteArray2<'_, f64>,
+        mut market_rate: PyReadwriteArray2<'_, f64>,
+    ) -> PyResult<()> {
+        self.inner.compute(parse_method(method)?).map_err(ftp_err)?;
+
+        write_into(
+            &mut stock_amort,
+            require_output(self.inner.stock_amort(), "stock_amort")?,
+            "stock_amort",
+        )?;
+        write_into(
+            &mut stock_instal,
+            require_output(self.inner.stock_instal(), "stock_instal")?,
+            "stock_instal",
+        )?;
+        write_into(
+            &mut varstock_amort,
+            require_output(self.inner.varstock_amort(), "varstock_amort")?,
+            "varstock_amort",
+        )?;
+        write_into(
+            &mut varstock_instal,
+            require_output(self.inner.varstock_instal(), "varstock_instal")?,
+            "varstock_instal",
+        )?;
+        write_into(
+            &mut ftp_rate,
+            require_output(self.inner.ftp_rate(), "ftp_rate")?,
+            "ftp_rate",
+        )?;
+        write_into(
+            &mut ftp_int,
+            require_output(self.inner.ftp_int(), "ftp_int")?,
+            "ftp_int",
+        )?;
+        write_into(
+            &mut market_rate,
+            require_output(self.inner.market_rate(), "market_rate")?,
+            "market_rate",
+        )?;
+        Ok(())
     }
 
     /// (rows, cols) of the profile matrix.
@@ -185,10 +308,232 @@ fn compute_flux<'py>(
     run_compute(py, outstanding, profiles, rates, ComputeMethod::Flux)
 }
 
+#[allow(clippy::too_many_arguments)]
+fn run_compute_into(
+    outstanding: PyReadonlyArray2<'_, f64>,
+    profiles: PyReadonlyArray2<'_, f64>,
+    rates: PyReadonlyArray2<'_, f64>,
+    method: ComputeMethod,
+    mut stock_amort: PyReadwriteArray2<'_, f64>,
+    mut stock_instal: PyReadwriteArray2<'_, f64>,
+    mut varstock_amort: PyReadwriteArray2<'_, f64>,
+    mut varstock_instal: PyReadwriteArray2<'_, f64>,
+    mut ftp_rate: PyReadwriteArray2<'_, f64>,
+    mut ftp_int: PyReadwriteArray2<'_, f64>,
+    mut market_rate: PyReadwriteArray2<'_, f64>,
+) -> PyResult<()> {
+    let mut r = FtpResult::new(
+        outstanding.as_array().to_owned(),
+        profiles.as_array().to_owned(),
+        rates.as_array().to_owned(),
+    );
+    r.compute(method).map_err(ftp_err)?;
+
+    write_into(&mut stock_amort, r.stock_amort().unwrap(), "stock_amort")?;
+    write_into(&mut stock_instal, r.stock_instal().unwrap(), "stock_instal")?;
+    write_into(
+        &mut varstock_amort,
+        r.varstock_amort().unwrap(),
+        "varstock_amort",
+    )?;
+    write_into(
+        &mut varstock_instal,
+        r.varstock_instal().unwrap(),
+        "varstock_instal",
+    )?;
+    write_into(&mut ftp_rate, r.ftp_rate().unwrap(), "ftp_rate")?;
+    write_into(&mut ftp_int, r.ftp_int().unwrap(), "ftp_int")?;
+    write_into(&mut market_rate, r.market_rate().unwrap(), "market_rate")?;
+    Ok(())
+}
+
+/// Compute FTP using the stock method, writing results directly into
+/// caller-provided numpy buffers instead of returning a fresh dict.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn compute_stock_into(
+    outstanding: PyReadonlyArray2<'_, f64>,
+    profiles: PyReadonlyArray2<'_, f64>,
+    rates: PyReadonlyArray2<'_, f64>,
+    stock_amort: PyReadwriteArray2<'_, f64>,
+    stock_instal: PyReadwriteArray2<'_, f64>,
+    varstock_amort: PyReadwriteArray2<'_, f64>,
+    varstock_instal: PyReadwriteArray2<'_, f64>,
+    ftp_rate: PyReadwriteArray2<'_, f64>,
+    ftp_int: PyReadwriteArray2<'_, f64>,
+    market_rate: PyReadwriteArray2<'_, f64>,
+) -> PyResult<()> {
+    run_compute_into(
+        outstanding,
+        profiles,
+        rates,
+        ComputeMethod::Stock,
+        stock_amort,
+        stock_instal,
+        varstock_amort,
+        varstock_instal,
+        ftp_rate,
+        ftp_int,
+        market_rate,
+    )
+}
+
+/// Compute FTP using the flux method, writing results directly into
+/// caller-provided numpy buffers instead of returning a fresh dict.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn compute_flux_into(
+    outstanding: PyReadonlyArray2<'_, f64>,
+    profiles: PyReadonlyArray2<'_, f64>,
+    rates: PyReadonlyArray2<'_, f64>,
+    stock_amort: PyReadwriteArray2<'_, f64>,
+    stock_instal: PyReadwriteArray2<'_, f64>,
+    varstock_amort: PyReadwriteArray2<'_, f64>,
+    varstock_instal: PyReadwriteArray2<'_, f64>,
+    ftp_rate: PyReadwriteArray2<'_, f64>,
+    ftp_int: PyReadwriteArray2<'_, f64>,
+    market_rate: PyReadwriteArray2<'_, f64>,
+) -> PyResult<()> {
+    run_compute_into(
+        outstanding,
+        profiles,
+        rates,
+        ComputeMethod::Flux,
+        stock_amort,
+        stock_instal,
+        varstock_amort,
+        varstock_instal,
+        ftp_rate,
+        ftp_int,
+        market_rate,
+    )
+}
+
+/// Slices batch element `b` out of an N-d array laid out `[batch, nrows,
+/// ncols]` into an owned 2D `(nrows, ncols)` array.
+fn batch_slice_2d(arr: &ArrayD<f64>, b: usize) -> PyResult<Array2<f64>> {
+    let view = arr.index_axis(Axis(0), b);
+    let view2 = view.into_dimensionality::<Ix2>().map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err(
+            "expected a 3D array with shape [batch, nrows, ncols]",
+        )
+    })?;
+    Ok(view2.to_owned())
+}
+
+/// Batched FTP computation over `batch` independent portfolios in one FFI
+/// crossing. `outstanding`/`profiles`/`rates` are 3D arrays shaped
+/// `[batch, nrows, 1]` / `[batch, nrows, ncols]` / `[batch, nrows,
+/// ncols - 1]`; each batch element is sliced into an owned `Array2`, run
+/// through the existing engine independently, and the seven outputs are
+/// stacked back into `[batch, nrows, ncols]` numpy arrays.
+///
+/// Behind the `rayon` feature, batch elements run on a thread pool (see
+/// `ftp_core::parallel`'s own rayon gate) instead of a sequential loop;
+/// non-`rayon` builds stay allocation-minimal.
+#[pyfunction]
+fn compute_batch<'py>(
+    py: Python<'py>,
+    method: &str,
+    outstanding: PyReadonlyArrayDyn<'py, f64>,
+    profiles: PyReadonlyArrayDyn<'py, f64>,
+    rates: PyReadonlyArrayDyn<'py, f64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let method = parse_method(method)?;
+    let outstanding = outstanding.as_array().to_owned();
+    let profiles = profiles.as_array().to_owned();
+    let rates = rates.as_array().to_owned();
+
+    let shape = profiles.shape();
+    if shape.len() != 3 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "'profiles' must be a 3D array with shape [batch, nrows, ncols]",
+        ));
+    }
+    let (batch, nrows, ncols) = (shape[0], shape[1], shape[2]);
+
+    let outstanding_shape = outstanding.shape();
+    if outstanding_shape != [batch, nrows, 1] {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "'outstanding' has shape {outstanding_shape:?}, expected [{batch}, {nrows}, 1]"
+        )));
+    }
+    let rates_shape = rates.shape();
+    if rates_shape != [batch, nrows, ncols - 1] {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "'rates' has shape {rates_shape:?}, expected [{batch}, {nrows}, {}]",
+            ncols - 1
+        )));
+    }
+
+    let run_one = |b: usize| -> PyResult<[Array2<f64>; 7]> {
+        let o = batch_slice_2d(&outstanding, b)?;
+        let p = batch_slice_2d(&profiles, b)?;
+        let rt = batch_slice_2d(&rates, b)?;
+        let mut r = FtpResult::new(o, p, rt);
+        r.compute(method).map_err(ftp_err)?;
+        Ok([
+            r.stock_amort().unwrap().clone(),
+            r.stock_instal().unwrap().clone(),
+            r.varstock_amort().unwrap().clone(),
+            r.varstock_instal().unwrap().clone(),
+            r.ftp_rate().unwrap().clone(),
+            r.ftp_int().unwrap().clone(),
+            r.market_rate().unwrap().clone(),
+        ])
+    };
+
+    #[cfg(feature = "rayon")]
+    let results: Vec<PyResult<[Array2<f64>; 7]>> = {
+        use rayon::prelude::*;
+        (0..batch).into_par_iter().map(run_one).collect()
+    };
+    #[cfg(not(feature = "rayon"))]
+    let results: Vec<PyResult<[Array2<f64>; 7]>> = (0..batch).map(run_one).collect();
+
+    let mut stock_amort = Array3::<f64>::zeros((batch, nrows, ncols));
+    let mut stock_instal = Array3::<f64>::zeros((batch, nrows, ncols));
+    let mut varstock_amort = Array3::<f64>::zeros((batch, nrows, ncols));
+    let mut varstock_instal = Array3::<f64>::zeros((batch, nrows, ncols));
+    let mut ftp_rate = Array3::<f64>::zeros((batch, nrows, ncols));
+    let mut ftp_int = Array3::<f64>::zeros((batch, nrows, ncols));
+    let mut market_rate = Array3::<f64>::zeros((batch, nrows, ncols));
+
+    for (b, outs) in results.into_iter().enumerate() {
+        let [sa, si, va, vi, fr, fi, mr] = outs?;
+        stock_amort.index_axis_mut(Axis(0), b).assign(&sa);
+        stock_instal.index_axis_mut(Axis(0), b).assign(&si);
+        varstock_amort.index_axis_mut(Axis(0), b).assign(&va);
+        varstock_instal.index_axis_mut(Axis(0), b).assign(&vi);
+        ftp_rate.index_axis_mut(Axis(0), b).assign(&fr);
+        ftp_int.index_axis_mut(Axis(0), b).assign(&fi);
+        market_rate.index_axis_mut(Axis(0), b).assign(&mr);
+    }
+
+    let dict = PyDict::new(py);
+    dict.set_item("stock_amort", stock_amort.into_dyn().into_pyarray(py))?;
+    dict.set_item("stock_instal", stock_instal.into_dyn().into_pyarray(py))?;
+    dict.set_item(
+        "varstock_amort",
+        varstock_amort.into_dyn().into_pyarray(py),
+    )?;
+    dict.set_item(
+        "varstock_instal",
+        varstock_instal.into_dyn().into_pyarray(py),
+    )?;
+    dict.set_item("ftp_rate", ftp_rate.into_dyn().into_pyarray(py))?;
+    dict.set_item("ftp_int", ftp_int.into_dyn().into_pyarray(py))?;
+    dict.set_item("market_rate", market_rate.into_dyn().into_pyarray(py))?;
+    Ok(dict)
+}
+
 #[pymodule]
 fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<FtpCalculator>()?;
     m.add_function(wrap_pyfunction!(compute_stock, m)?)?;
     m.add_function(wrap_pyfunction!(compute_flux, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_stock_into, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_flux_into, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_batch, m)?)?;
     Ok(())
 }
@@ -0,0 +1,114 @@
+use ndarray::Array2;
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use ftp_calculator_core::{ComputeMethod, FtpError, FtpResult};
+
+/// Convert an FtpError into the `JsValue` string `compute()` rejects with.
+fn ftp_err(e: FtpError) -> JsValue {
+    JsValue::from_str(&e.to_string())
+}
+
+/// Helper: get a computed output or reject with a `JsValue` error string.
+fn require_output<'a>(opt: Option<&'a Array2<f64>>, name: &str) -> Result<&'a Array2<f64>, JsValue> {
+    opt.ok_or_else(|| JsValue::from_str(&format!("'{name}' not available — call compute() first")))
+}
+
+/// Builds an `(nrows, ncols)` `Array2<f64>` from a flat, row-major
+/// `Float64Array` — the same layout `Float64Array::to_vec` hands back.
+fn array2_from_flat(data: &Float64Array, nrows: usize, ncols: usize) -> Array2<f64> {
+    Array2::from_shape_vec((nrows, ncols), data.to_vec())
+        .expect("Float64Array length must match nrows * ncols")
+}
+
+/// FTP Calculator — wraps the Rust ftp_core engine for JS/TS callers, so
+/// browser/Node front-ends get the same create/compute/read workflow as
+/// the C and Python bindings without unsafe pointer marshalling.
+///
+/// Usage (JS):
+///     const calc = new FtpCalculator(outstanding, 1, 1, profiles, 1, 4, rates, 1, 3);
+///     calc.compute(0); // ComputeMethod::Stock
+///     const stockAmort = calc.stock_amort(); // Float64Array, row-major
+///     const [rows, cols] = calc.dims();
+#[wasm_bindgen]
+pub struct FtpCalculator {
+    inner: FtpResult,
+}
+
+#[wasm_bindgen]
+impl FtpCalculator {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        outstanding: Float64Array,
+        outstanding_rows: usize,
+        outstanding_cols: usize,
+        profiles: Float64Array,
+        profiles_rows: usize,
+        profiles_cols: usize,
+        rates: Float64Array,
+        rates_rows: usize,
+        rates_cols: usize,
+    ) -> FtpCalculator {
+        FtpCalculator {
+            inner: FtpResult::new(
+                array2_from_flat(&outstanding, outstanding_rows, outstanding_cols),
+                array2_from_flat(&profiles, profiles_rows, profiles_cols),
+                array2_from_flat(&rates, rates_rows, rates_cols),
+            ),
+        }
+    }
+
+    /// Run the FTP computation. `method` is `0` for stock, `1` for flux —
+    /// see `ComputeMethod`.
+    pub fn compute(&mut self, method: u8) -> Result<(), JsValue> {
+        let m = match method {
+            0 => ComputeMethod::Stock,
+            1 => ComputeMethod::Flux,
+            other => return Err(JsValue::from_str(&format!("unknown method code {other} — use 0 (stock) or 1 (flux)"))),
+        };
+        self.inner.compute(m).map_err(ftp_err)
+    }
+
+    /// `[rows, cols]` of the profile matrix.
+    pub fn dims(&self) -> Vec<usize> {
+        let (r, c) = self.inner.input_profiles().dim();
+        vec![r, c]
+    }
+
+    // --- output getters (return freshly allocated Float64Arrays, row-major) ---
+
+    pub fn stock_amort(&self) -> Result<Float64Array, JsValue> {
+        let arr = require_output(self.inner.stock_amort(), "stock_amort")?;
+        Ok(Float64Array::from(arr.iter().copied().collect::<Vec<_>>().as_slice()))
+    }
+
+    pub fn stock_instal(&self) -> Result<Float64Array, JsValue> {
+        let arr = require_output(self.inner.stock_instal(), "stock_instal")?;
+        Ok(Float64Array::from(arr.iter().copied().collect::<Vec<_>>().as_slice()))
+    }
+
+    pub fn varstock_amort(&self) -> Result<Float64Array, JsValue> {
+        let arr = require_output(self.inner.varstock_amort(), "varstock_amort")?;
+        Ok(Float64Array::from(arr.iter().copied().collect::<Vec<_>>().as_slice()))
+    }
+
+    pub fn varstock_instal(&self) -> Result<Float64Array, JsValue> {
+        let arr = require_output(self.inner.varstock_instal(), "varstock_instal")?;
+        Ok(Float64Array::from(arr.iter().copied().collect::<Vec<_>>().as_slice()))
+    }
+
+    pub fn ftp_rate(&self) -> Result<Float64Array, JsValue> {
+        let arr = require_output(self.inner.ftp_rate(), "ftp_rate")?;
+        Ok(Float64Array::from(arr.iter().copied().collect::<Vec<_>>().as_slice()))
+    }
+
+    pub fn ftp_int(&self) -> Result<Float64Array, JsValue> {
+        let arr = require_output(self.inner.ftp_int(), "ftp_int")?;
+        Ok(Float64Array::from(arr.iter().copied().collect::<Vec<_>>().as_slice()))
+    }
+
+    pub fn market_rate(&self) -> Result<Float64Array, JsValue> {
+        let arr = require_output(self.inner.market_rate(), "market_rate")?;
+        Ok(Float64Array::from(arr.iter().copied().collect::<Vec<_>>().as_slice()))
+    }
+}
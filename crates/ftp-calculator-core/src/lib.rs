@@ -21,11 +21,26 @@
 //! result.compute(ComputeMethod::Stock).unwrap();
 //! ```
 
+mod curve;
 mod error;
 mod flux;
+#[cfg(feature = "polars")]
+mod io;
+mod matrix;
+mod na;
+mod numeric;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod result;
+pub mod small;
 mod stock;
 mod utils;
 
+pub use crate::curve::{CurveInterpolation, RateCurve};
 pub use crate::error::FtpError;
+#[cfg(feature = "polars")]
+pub use crate::io::ColumnSpec;
+pub use crate::matrix::{FtpMatrix, Matrix};
+pub use crate::na::{is_na, is_nan, NaSentinel};
+pub use crate::numeric::FtpFloat;
 pub use crate::result::{ComputeMethod, FtpResult};
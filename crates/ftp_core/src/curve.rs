@@ -0,0 +1,135 @@
+//! Term-structure yield curve input, interpolated onto the per-bucket rate
+//! grid `compute_rates` consumes.
+//!
+//! Callers often have rates at a handful of market tenors (1M, 3M, 6M, 1Y,
+//! 2Y, ...) per row rather than a fully materialized `ncols - 1`-column
+//! `input_rate` matrix. [`RateCurve`] holds those sparse per-row
+//! `(tenor, rate)` nodes and expands them to the dense grid
+//! [`FtpResult::new`] expects via [`RateCurve::to_rate_matrix`].
+
+use ndarray::Array2;
+
+use crate::numeric::FtpFloat;
+
+/// Interpolation method used to fill gaps between curve nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveInterpolation {
+    /// Linear interpolation directly on rates.
+    Linear,
+    /// Linear interpolation on the log of the implied discount factors
+    /// `exp(-rate * tenor)` (geometric interpolation of the discount factors
+    /// themselves), converted back to a rate afterwards. Produces
+    /// smoother forward rates than linear-on-rate for widely spaced nodes.
+    LogLinearDiscount,
+}
+
+/// A sparse per-row term structure: `(tenor_in_months, rate)` nodes,
+/// interpolated onto the dense per-bucket grid `compute_rates` consumes.
+/// Beyond the last node, the curve extrapolates flat at the last rate.
+#[derive(Debug, Clone)]
+pub struct RateCurve<T: FtpFloat> {
+    /// One row per portfolio row, each a list of `(tenor, rate)` nodes
+    /// (sorted by tenor on construction).
+    nodes: Vec<Vec<(T, T)>>,
+    interpolation: CurveInterpolation,
+}
+
+impl<T: FtpFloat> RateCurve<T> {
+    /// Builds a curve from per-row `(tenor, rate)` nodes. Each row's nodes
+    /// are sorted by tenor; every row must have at least one node.
+    pub fn new(nodes: Vec<Vec<(T, T)>>, interpolation: CurveInterpolation) -> Self {
+        let mut nodes = nodes;
+        for row in &mut nodes {
+            row.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        }
+        Self { nodes, interpolation }
+    }
+
+    /// Expands this curve to a dense `(nrows, ncols)` rate matrix, one
+    /// column per monthly bucket `1..=ncols`, ready to feed
+    /// [`FtpResult::new`](crate::FtpResult::new) as `input_rate`.
+    pub fn to_rate_matrix(&self, ncols: usize) -> Array2<T> {
+        let nrows = self.nodes.len();
+        let mut out = Array2::<T>::zeros((nrows, ncols));
+        for (i, row_nodes) in self.nodes.iter().enumerate() {
+            for j in 0..ncols {
+                let tenor = T::from(j + 1).unwrap();
+                out[[i, j]] = interpolate(row_nodes, tenor, self.interpolation);
+            }
+        }
+        out
+    }
+}
+
+fn interpolate<T: FtpFloat>(nodes: &[(T, T)], tenor: T, method: CurveInterpolation) -> T {
+    debug_assert!(!nodes.is_empty(), "RateCurve row must have at least one node");
+
+    if tenor <= nodes[0].0 {
+        return nodes[0].1;
+    }
+    if tenor >= nodes[nodes.len() - 1].0 {
+        return nodes[nodes.len() - 1].1;
+    }
+
+    let idx = nodes.partition_point(|&(t, _)| t <= tenor);
+    let (t0, r0) = nodes[idx - 1];
+    let (t1, r1) = nodes[idx];
+    let frac = (tenor - t0) / (t1 - t0);
+
+    match method {
+        CurveInterpolation::Linear => r0 + (r1 - r0) * frac,
+        CurveInterpolation::LogLinearDiscount => {
+            let df0 = (-r0 * t0).exp();
+            let df1 = (-r1 * t1).exp();
+            let df = df0 * (df1 / df0).powf(frac);
+            -(df.ln()) / tenor
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_interpolation_matches_nodes_exactly() {
+        let curve = RateCurve::<f64>::new(
+            vec![vec![(1.0, 0.01), (12.0, 0.03)]],
+            CurveInterpolation::Linear,
+        );
+        let m = curve.to_rate_matrix(12);
+        assert!((m[[0, 0]] - 0.01).abs() < 1e-9);
+        assert!((m[[0, 11]] - 0.03).abs() < 1e-9);
+    }
+
+    #[test]
+    fn linear_interpolation_midpoint() {
+        let curve = RateCurve::<f64>::new(
+            vec![vec![(1.0, 0.01), (11.0, 0.03)]],
+            CurveInterpolation::Linear,
+        );
+        let m = curve.to_rate_matrix(11);
+        // tenor=6 months is exactly halfway between the 1M and 11M nodes.
+        assert!((m[[0, 5]] - 0.02).abs() < 1e-9);
+    }
+
+    #[test]
+    fn flat_extrapolation_beyond_last_node() {
+        let curve = RateCurve::new(vec![vec![(1.0, 0.02)]], CurveInterpolation::Linear);
+        let m = curve.to_rate_matrix(6);
+        for j in 0..6 {
+            assert_eq!(m[[0, j]], 0.02);
+        }
+    }
+
+    #[test]
+    fn log_linear_discount_matches_nodes_exactly() {
+        let curve = RateCurve::<f64>::new(
+            vec![vec![(1.0, 0.01), (24.0, 0.025)]],
+            CurveInterpolation::LogLinearDiscount,
+        );
+        let m = curve.to_rate_matrix(24);
+        assert!((m[[0, 0]] - 0.01).abs() < 1e-9);
+        assert!((m[[0, 23]] - 0.025).abs() < 1e-9);
+    }
+}
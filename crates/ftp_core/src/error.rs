@@ -2,6 +2,7 @@ use std::fmt;
 
 /// Errors that can occur during FTP calculations.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FtpError {
     /// Input matrices have different numbers of rows.
     DimensionMismatch {
@@ -15,6 +16,19 @@ pub enum FtpError {
         rate_cols: usize,
         profile_cols: usize,
     },
+    /// A DataFrame/CSV source was missing an expected column.
+    #[cfg(feature = "polars")]
+    ColumnNotFound { name: String },
+    /// The underlying columnar I/O operation failed (CSV parsing, DataFrame construction, ...).
+    #[cfg(feature = "polars")]
+    Io(String),
+    /// `recompute_from_row` was called before `compute()` ever ran.
+    NotComputed,
+    /// `recompute_from_row` was given a row outside `0..nrows`.
+    RowOutOfBounds { row: usize, nrows: usize },
+    /// `to_json`/`from_json` (de)serialization failed.
+    #[cfg(feature = "serde")]
+    SerdeError(String),
 }
 
 impl fmt::Display for FtpError {
@@ -41,6 +55,20 @@ impl fmt::Display for FtpError {
                     rate_cols, profile_cols
                 )
             }
+            #[cfg(feature = "polars")]
+            FtpError::ColumnNotFound { name } => {
+                write!(f, "column '{}' not found in DataFrame", name)
+            }
+            #[cfg(feature = "polars")]
+            FtpError::Io(msg) => write!(f, "columnar I/O error: {}", msg),
+            FtpError::NotComputed => {
+                write!(f, "recompute_from_row called before compute() ever ran")
+            }
+            FtpError::RowOutOfBounds { row, nrows } => {
+                write!(f, "row {} out of bounds for {} rows", row, nrows)
+            }
+            #[cfg(feature = "serde")]
+            FtpError::SerdeError(msg) => write!(f, "serde (de)serialization error: {}", msg),
         }
     }
 }
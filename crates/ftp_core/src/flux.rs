@@ -1,14 +1,25 @@
-use ndarray::s;
-
+use crate::na::NaSentinel;
 use crate::result::FtpResult;
-use crate::stock::compute_rates;
-use crate::utils::extract_anti_diagonal_rect2;
+use crate::stock::compute_rates_from_row;
 
 /// Executes the **flux** method computation on an `FtpResult`.
 ///
 /// All output matrices must already be initialised (via `compute()`).
-pub(crate) fn compute_flux(r: &mut FtpResult, nrows: usize, ncols: usize) {
-    for i in 0..nrows {
+pub(crate) fn compute_flux<T: NaSentinel>(r: &mut FtpResult<T>, nrows: usize, ncols: usize) {
+    compute_flux_from_row(r, nrows, ncols, 0);
+}
+
+/// Same as [`compute_flux`], but only (re)computes rows `from_row..nrows`.
+/// `flux_stock_var`'s column-0 front amount sums over *earlier* rows'
+/// `varstock_amort`, so this is safe as long as rows `0..from_row` are
+/// already up to date — see [`FtpResult::recompute_from_row`].
+pub(crate) fn compute_flux_from_row<T: NaSentinel>(
+    r: &mut FtpResult<T>,
+    nrows: usize,
+    ncols: usize,
+    from_row: usize,
+) {
+    for i in from_row..nrows {
         for j in 0..ncols {
             // 1. New product (varstock_amort)
             flux_stock_var(r, i, j);
@@ -22,77 +33,38 @@ pub(crate) fn compute_flux(r: &mut FtpResult, nrows: usize, ncols: usize) {
     }
 
     // 5. ftp_rate, ftp_int, market_rate (same as stock method)
-    compute_rates(r, nrows, ncols);
+    compute_rates_from_row(r, nrows, ncols, from_row);
 }
 
-/// Flux method: new product (varstock_amort).
-///
-/// - Row 0:     `profile[i,j] * outstanding[i,0]`
-/// - Col 0:     `max(0, outstanding[i,0] - sum of varstock_amort[i-k, k] for k=1..i)`
-/// - Otherwise: `varstock_amort[i,0] * profile[i,j]`
-fn flux_stock_var(r: &mut FtpResult, rownum: usize, colnum: usize) {
-    let outstanding = &r.input_outstanding;
-    let profiles = &r.input_profiles;
-
-    let value = if rownum == 0 {
-        profiles[[rownum, colnum]] * outstanding[[rownum, 0]]
-    } else if colnum == 0 {
-        let va = r.varstock_amort.as_ref().unwrap();
-        let mut front_amt: f64 = 0.0;
-        for i in 1..=rownum {
-            front_amt += va[[rownum - i, i]];
-        }
-        front_amt = outstanding[[rownum, 0]] - front_amt;
-        if front_amt < 0.0 {
-            0.0
-        } else {
-            front_amt
-        }
-    } else {
-        let va = r.varstock_amort.as_ref().unwrap();
-        va[[rownum, 0]] * profiles[[rownum, colnum]]
-    };
-
-    r.varstock_amort.as_mut().unwrap()[[rownum, colnum]] = value;
+/// Flux method: new product (varstock_amort). Delegates to
+/// [`crate::small::flux_stock_var`] (the [`FtpMatrix`]-generic
+/// implementation) over `r`'s `ndarray` backend, so the `ndarray`-backed and
+/// const-generic [`crate::matrix::Matrix`]-backed engines run the exact same
+/// algorithm rather than two copies that could drift apart.
+fn flux_stock_var<T: NaSentinel>(r: &mut FtpResult<T>, rownum: usize, colnum: usize) {
+    let va = r.varstock_amort.as_mut().unwrap();
+    crate::small::flux_stock_var(&r.input_outstanding, &r.input_profiles, va, rownum, colnum);
 }
 
-/// varstock_instal[i,0] = 0
-/// varstock_instal[i,j] = varstock_amort[i,j-1] - varstock_amort[i,j]  for j > 0
-fn flux_varstock_instal(r: &mut FtpResult, rownum: usize, colnum: usize) {
-    if colnum > 0 {
-        let va = r.varstock_amort.as_ref().unwrap();
-        let val = va[[rownum, colnum - 1]] - va[[rownum, colnum]];
-        r.varstock_instal.as_mut().unwrap()[[rownum, colnum]] = val;
-    }
+/// See [`crate::small::flux_varstock_instal`].
+fn flux_varstock_instal<T: NaSentinel>(r: &mut FtpResult<T>, rownum: usize, colnum: usize) {
+    let va = r.varstock_amort.as_ref().unwrap();
+    let vi = r.varstock_instal.as_mut().unwrap();
+    crate::small::flux_varstock_instal::<T, _, _>(va, vi, rownum, colnum);
 }
 
-/// Flux method: stock_amort via anti-diagonal sums.
-///
-/// - Row 0: `stock_amort[0,j] = varstock_amort[0,j]`
-/// - Row >0: sum of anti-diagonal of `varstock_amort[0..=i, j..ncols]`
-fn flux_stock_amort(r: &mut FtpResult, rownum: usize, colnum: usize) {
+/// See [`crate::small::flux_stock_amort`].
+fn flux_stock_amort<T: NaSentinel>(r: &mut FtpResult<T>, rownum: usize, colnum: usize) {
     let va = r.varstock_amort.as_ref().unwrap();
-
-    let value = if rownum == 0 {
-        va[[rownum, colnum]]
-    } else {
-        let (_, ncols) = va.dim();
-        let slice = va.slice(s![0..rownum + 1, colnum..ncols]);
-        let diag = extract_anti_diagonal_rect2(&slice);
-        diag.iter().sum::<f64>()
-    };
-
-    r.stock_amort.as_mut().unwrap()[[rownum, colnum]] = value;
+    let sa = r.stock_amort.as_mut().unwrap();
+    crate::small::flux_stock_amort::<T, _, _>(va, sa, rownum, colnum);
 }
 
-/// stock_instal[i,0] = 0
-/// stock_instal[i,j] = stock_amort[i,j-1] - stock_amort[i,j]  for j > 0
-fn flux_stock_instal(r: &mut FtpResult, rownum: usize, colnum: usize) {
-    if colnum > 0 {
-        let sa = r.stock_amort.as_ref().unwrap();
-        let val = sa[[rownum, colnum - 1]] - sa[[rownum, colnum]];
-        r.stock_instal.as_mut().unwrap()[[rownum, colnum]] = val;
-    }
+/// See [`crate::small::flux_stock_instal`].
+fn flux_stock_instal<T: NaSentinel>(r: &mut FtpResult<T>, rownum: usize, colnum: usize) {
+    let sa = r.stock_amort.as_ref().unwrap();
+    let si = r.stock_instal.as_mut().unwrap();
+    crate::small::flux_stock_instal::<T, _, _>(sa, si, rownum, colnum);
 }
 
 #[cfg(test)]
@@ -129,4 +101,37 @@ mod tests {
         assert_eq!(sa[[0, 1]], va[[0, 1]]);
         assert_eq!(sa[[0, 2]], va[[0, 2]]);
     }
+
+    #[test]
+    fn test_flux_stock_var_f32() {
+        let mut r = FtpResult::<f32>::new(
+            array![[800.0f32]],
+            array![[1.00, 0.60, 0.30]],
+            array![[0.01200, 0.01300]],
+        );
+        r.compute(ComputeMethod::Flux).unwrap();
+        let va = r.varstock_amort().unwrap();
+        assert_eq!(va[[0, 0]], 800.0f32);
+    }
+
+    #[test]
+    fn test_flux_na_outstanding_skips_missing_vintage_in_front_amt() {
+        use crate::na::NaSentinel;
+
+        let mut r = FtpResult::new(
+            array![[800.0], [f64::na()], [900.0]],
+            array![[1.00, 0.60, 0.30], [1.00, 0.60, 0.30], [1.00, 0.60, 0.30]],
+            array![[0.012, 0.013], [0.012, 0.013], [0.012, 0.013]],
+        );
+        r.compute(ComputeMethod::Flux).unwrap();
+        let va = r.varstock_amort().unwrap();
+
+        // Row 1 directly depends on its own (NA) outstanding: NA propagates.
+        assert!(va[[1, 0]].is_na());
+        assert!(va[[1, 1]].is_na());
+
+        // Row 2's col-0 front amount skips row 1's NA contribution instead
+        // of turning the whole column NA.
+        assert!(!va[[2, 0]].is_na());
+    }
 }
@@ -0,0 +1,139 @@
+//! Columnar I/O: build an [`FtpResult`] straight from a Polars `DataFrame`
+//! (or a CSV file) instead of hand-assembling `Array2` matrices, and export
+//! computed outputs back into a tidy long-format `DataFrame`.
+//!
+//! Gated behind the `polars` feature so the core engine stays dependency-free
+//! for callers who only need the matrix API.
+#![cfg(feature = "polars")]
+
+use ndarray::Array2;
+use polars::prelude::*;
+
+use crate::error::FtpError;
+use crate::result::FtpResult;
+
+/// Column-name configuration for [`FtpResult::from_dataframe`].
+///
+/// One row of the DataFrame is one cohort (a row of `input_profiles`). The
+/// profile/rate matrices are represented as `{prefix}0`, `{prefix}1`, ...
+/// columns, one per tenor bucket.
+pub struct ColumnSpec<'a> {
+    pub outstanding: &'a str,
+    pub profile_prefix: &'a str,
+    pub rate_prefix: &'a str,
+}
+
+impl<'a> ColumnSpec<'a> {
+    fn profile_col(&self, j: usize) -> String {
+        format!("{}{}", self.profile_prefix, j)
+    }
+
+    fn rate_col(&self, j: usize) -> String {
+        format!("{}{}", self.rate_prefix, j)
+    }
+}
+
+fn f64_column(df: &DataFrame, name: &str) -> Result<Vec<f64>, FtpError> {
+    let col = df
+        .column(name)
+        .map_err(|_| FtpError::ColumnNotFound { name: name.to_string() })?;
+    col.f64()
+        .map_err(|e| FtpError::Io(e.to_string()))?
+        .into_iter()
+        .map(|v| v.ok_or_else(|| FtpError::Io(format!("null value in column '{}'", name))))
+        .collect()
+}
+
+impl FtpResult<f64> {
+    /// Builds an `FtpResult` from a `DataFrame` where one row is one cohort.
+    ///
+    /// `spec.outstanding` names the outstanding-balance column, and
+    /// `spec.profile_prefix` / `spec.rate_prefix` name the `{prefix}0`,
+    /// `{prefix}1`, ... tenor-bucket columns that are gathered into the
+    /// `input_profiles` / `input_rate` matrices.
+    pub fn from_dataframe(df: &DataFrame, spec: &ColumnSpec) -> Result<Self, FtpError> {
+        let nrows = df.height();
+        let outstanding = f64_column(df, spec.outstanding)?;
+
+        let mut ncols_profiles = 0;
+        while df.column(&spec.profile_col(ncols_profiles)).is_ok() {
+            ncols_profiles += 1;
+        }
+        let mut profile_cols = Vec::with_capacity(ncols_profiles);
+        for j in 0..ncols_profiles {
+            profile_cols.push(f64_column(df, &spec.profile_col(j))?);
+        }
+
+        let mut ncols_rate = 0;
+        while df.column(&spec.rate_col(ncols_rate)).is_ok() {
+            ncols_rate += 1;
+        }
+        let mut rate_cols = Vec::with_capacity(ncols_rate);
+        for j in 0..ncols_rate {
+            rate_cols.push(f64_column(df, &spec.rate_col(j))?);
+        }
+
+        let input_outstanding = Array2::from_shape_fn((nrows, 1), |(i, _)| outstanding[i]);
+        let input_profiles = Array2::from_shape_fn((nrows, ncols_profiles), |(i, j)| profile_cols[j][i]);
+        let input_rate = Array2::from_shape_fn((nrows, ncols_rate), |(i, j)| rate_cols[j][i]);
+
+        Ok(FtpResult::new(input_outstanding, input_profiles, input_rate))
+    }
+
+    /// Builds an `FtpResult` straight from a CSV file.
+    ///
+    /// See [`FtpResult::from_dataframe`] for the column naming convention.
+    pub fn from_csv(path: &str, spec: &ColumnSpec) -> Result<Self, FtpError> {
+        let df = CsvReadOptions::default()
+            .with_has_header(true)
+            .try_into_reader_with_file_path(Some(path.into()))
+            .map_err(|e| FtpError::Io(e.to_string()))?
+            .finish()
+            .map_err(|e| FtpError::Io(e.to_string()))?;
+        Self::from_dataframe(&df, spec)
+    }
+
+    /// Serializes all computed outputs into a tidy long-format `DataFrame`
+    /// with `(row, col, metric, value)` columns, one row per output cell.
+    ///
+    /// Errors if `compute()` hasn't run yet.
+    pub fn to_long_dataframe(&self) -> Result<DataFrame, FtpError> {
+        let outputs: [(&str, Option<&Array2<f64>>); 7] = [
+            ("stock_amort", self.stock_amort()),
+            ("stock_instal", self.stock_instal()),
+            ("varstock_amort", self.varstock_amort()),
+            ("varstock_instal", self.varstock_instal()),
+            ("ftp_rate", self.ftp_rate()),
+            ("ftp_int", self.ftp_int()),
+            ("market_rate", self.market_rate()),
+        ];
+
+        let mut rows: Vec<i64> = Vec::new();
+        let mut cols: Vec<i64> = Vec::new();
+        let mut metrics: Vec<&str> = Vec::new();
+        let mut values: Vec<f64> = Vec::new();
+
+        for (name, mat) in outputs {
+            let mat = mat.ok_or_else(|| {
+                FtpError::Io(format!("'{}' not available — call compute() first", name))
+            })?;
+            let (nrows, ncols) = mat.dim();
+            for i in 0..nrows {
+                for j in 0..ncols {
+                    rows.push(i as i64);
+                    cols.push(j as i64);
+                    metrics.push(name);
+                    values.push(mat[[i, j]]);
+                }
+            }
+        }
+
+        DataFrame::new(vec![
+            Column::new("row".into(), rows),
+            Column::new("col".into(), cols),
+            Column::new("metric".into(), metrics),
+            Column::new("value".into(), values),
+        ])
+        .map_err(|e| FtpError::Io(e.to_string()))
+    }
+}
@@ -0,0 +1,180 @@
+//! Const-generic stack-allocated matrix backend for small, compile-time-sized
+//! grids (e.g. 12×12 monthly buckets), where the heap `Array2` allocation and
+//! the repeated `.as_ref().unwrap()` churn in [`crate::flux`]/[`crate::stock`]
+//! is wasteful relative to the grid size.
+use std::ops::{Index, IndexMut};
+
+/// Row-major matrix backed by `[[T; N]; M]` stack storage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix<T, const M: usize, const N: usize> {
+    data: [[T; N]; M],
+}
+
+impl<T: Default + Copy, const M: usize, const N: usize> Default for Matrix<T, M, N> {
+    fn default() -> Self {
+        Self {
+            data: [[T::default(); N]; M],
+        }
+    }
+}
+
+impl<T, const M: usize, const N: usize> Matrix<T, M, N> {
+    /// `(rows, cols)`, known at compile time.
+    pub const fn dim(&self) -> (usize, usize) {
+        (M, N)
+    }
+}
+
+impl<T, const M: usize, const N: usize> Index<(usize, usize)> for Matrix<T, M, N> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.data[i][j]
+    }
+}
+
+impl<T, const M: usize, const N: usize> IndexMut<(usize, usize)> for Matrix<T, M, N> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.data[i][j]
+    }
+}
+
+/// Minimal 2D-matrix interface shared by the heap `ndarray::Array2<T>`
+/// backend and the stack [`Matrix`] backend, so the small-grid flux routines
+/// in [`crate::small`] can run unmodified on either.
+pub trait FtpMatrix<T: Copy> {
+    fn dim(&self) -> (usize, usize);
+    fn get(&self, i: usize, j: usize) -> T;
+    fn set(&mut self, i: usize, j: usize, value: T);
+
+    /// Maps row `i` to row 0 if this matrix holds a single shared row (e.g.
+    /// one outstanding balance broadcast across every cohort), else `i`
+    /// unchanged.
+    fn broadcast_row(&self, i: usize) -> usize {
+        if self.dim().0 == 1 {
+            0
+        } else {
+            i
+        }
+    }
+
+    /// Sum of the anti-diagonal of `rows 0..=i, cols j..ncols`, skipping
+    /// cells `is_na` flags as absent rather than letting them poison the
+    /// sum. The default walks cell-by-cell through [`Self::get`]; backends
+    /// that can bound the diagonal length at compile time (e.g. [`Matrix`])
+    /// should override this to avoid the heap `Vec` a plain
+    /// iterator-and-filter version would otherwise need.
+    fn anti_diagonal_sum<F>(&self, i: usize, j: usize, is_na: F) -> T
+    where
+        F: Fn(T) -> bool,
+        T: core::ops::Add<Output = T> + num_traits::Zero,
+    {
+        let (_, ncols) = self.dim();
+        let nrows = i + 1;
+        let width = ncols - j;
+        let take = nrows.min(width);
+        let mut sum = T::zero();
+        for k in 0..take {
+            let v = self.get(nrows - k - 1, j + k);
+            if !is_na(v) {
+                sum = sum + v;
+            }
+        }
+        sum
+    }
+}
+
+impl<T: Copy + Default, const M: usize, const N: usize> FtpMatrix<T> for Matrix<T, M, N> {
+    fn dim(&self) -> (usize, usize) {
+        Matrix::dim(self)
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        self[(i, j)]
+    }
+
+    fn set(&mut self, i: usize, j: usize, value: T) {
+        self[(i, j)] = value;
+    }
+
+    fn anti_diagonal_sum<F>(&self, i: usize, j: usize, is_na: F) -> T
+    where
+        F: Fn(T) -> bool,
+        T: core::ops::Add<Output = T> + num_traits::Zero,
+    {
+        let (buf, len) = extract_anti_diagonal_const(self, i, j);
+        let mut sum = T::zero();
+        for &v in &buf[..len] {
+            if !is_na(v) {
+                sum = sum + v;
+            }
+        }
+        sum
+    }
+}
+
+impl<T: Copy> FtpMatrix<T> for ndarray::Array2<T> {
+    fn dim(&self) -> (usize, usize) {
+        ndarray::Array2::dim(self)
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        self[[i, j]]
+    }
+
+    fn set(&mut self, i: usize, j: usize, value: T) {
+        self[[i, j]] = value;
+    }
+}
+
+/// Const-matrix-aware anti-diagonal extraction: slices `rows 0..=i, cols
+/// j..N` without allocating a `Vec`, writing into a fixed-capacity
+/// `[T; M]` buffer instead. Returns the buffer plus the number of valid
+/// entries at its front.
+pub fn extract_anti_diagonal_const<T: Copy + Default, const M: usize, const N: usize>(
+    m: &Matrix<T, M, N>,
+    i: usize,
+    j: usize,
+) -> ([T; M], usize) {
+    let nrows = i + 1;
+    let ncols = N - j;
+    let mut out = [T::default(); M];
+    let mut len = 0;
+    let take = nrows.min(ncols);
+    for k in 0..take {
+        out[k] = m[(nrows - k - 1, j + k)];
+        len += 1;
+    }
+    (out, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matrix_default_is_zeroed() {
+        let m = Matrix::<f64, 3, 3>::default();
+        assert_eq!(m[(0, 0)], 0.0);
+        assert_eq!(m.dim(), (3, 3));
+    }
+
+    #[test]
+    fn matrix_index_mut_roundtrips() {
+        let mut m = Matrix::<f64, 2, 2>::default();
+        m[(1, 0)] = 42.0;
+        assert_eq!(m[(1, 0)], 42.0);
+    }
+
+    #[test]
+    fn extract_anti_diagonal_const_matches_square_case() {
+        let mut m = Matrix::<f64, 3, 3>::default();
+        for i in 0..3 {
+            for j in 0..3 {
+                m[(i, j)] = (i * 3 + j + 1) as f64;
+            }
+        }
+        let (buf, len) = extract_anti_diagonal_const(&m, 2, 0);
+        assert_eq!(&buf[..len], &[7.0, 5.0, 3.0]);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::numeric::FtpFloat;
+
+/// A float type that can carry a dedicated "missing data" sentinel.
+///
+/// Banking FTP inputs routinely have missing outstanding balances or missing
+/// rates for some tenors. Rather than reusing a plain `NaN` for that (which
+/// would make "this value is genuinely unknown" indistinguishable from "this
+/// value is the result of a broken computation"), each concrete float gets
+/// its own reserved NaN bit pattern as the NA sentinel. `is_na(x)` checks for
+/// that exact pattern; `x.is_nan()` is true for *any* NaN, NA included.
+///
+/// Implemented concretely for `f32`/`f64` rather than blanket over
+/// [`FtpFloat`], since the sentinel is a specific bit pattern per type.
+pub trait NaSentinel: FtpFloat {
+    /// The NA sentinel value for this type.
+    fn na() -> Self;
+    /// True if `self` is exactly the NA sentinel (not just any NaN).
+    fn is_na(self) -> bool;
+}
+
+impl NaSentinel for f64 {
+    fn na() -> Self {
+        f64::from_bits(0x7FF8_0000_0000_0001)
+    }
+
+    fn is_na(self) -> bool {
+        self.to_bits() == Self::na().to_bits()
+    }
+}
+
+impl NaSentinel for f32 {
+    fn na() -> Self {
+        f32::from_bits(0x7FC0_0001)
+    }
+
+    fn is_na(self) -> bool {
+        self.to_bits() == Self::na().to_bits()
+    }
+}
+
+/// True if `x` is the NA sentinel (missing data), as opposed to any other NaN.
+pub fn is_na<T: NaSentinel>(x: T) -> bool {
+    x.is_na()
+}
+
+/// True if `x` is any NaN, including the NA sentinel.
+pub fn is_nan<T: FtpFloat>(x: T) -> bool {
+    x.is_nan()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn na_is_distinguishable_from_plain_nan() {
+        let na = f64::na();
+        let nan = f64::NAN;
+        assert!(is_na(na));
+        assert!(is_nan(na));
+        assert!(!is_na(nan));
+        assert!(is_nan(nan));
+    }
+
+    #[test]
+    fn na_is_distinguishable_for_f32() {
+        let na = f32::na();
+        assert!(is_na(na));
+        assert!(!is_na(f32::NAN));
+    }
+}
@@ -0,0 +1,12 @@
+use ndarray::ScalarOperand;
+use num_traits::Float;
+
+/// Floating-point scalar usable throughout the FTP engine.
+///
+/// Blanket-implemented for any type that satisfies `num_traits::Float` plus
+/// the `ndarray` scalar-op bound the matrix routines rely on. In practice
+/// this is `f32` (half the memory, faster anti-diagonal sums on large
+/// portfolios) or `f64` (the historical default, kept precise).
+pub trait FtpFloat: Float + ScalarOperand + std::iter::Sum + 'static {}
+
+impl<T> FtpFloat for T where T: Float + ScalarOperand + std::iter::Sum + 'static {}
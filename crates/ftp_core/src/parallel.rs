@@ -0,0 +1,387 @@
+//! Rayon-backed parallel execution mode.
+//!
+//! The double loop in `compute_flux` and especially `compute_rates_from_row` are
+//! embarrassingly parallel across independent cells once the computation is
+//! split into its dependency stages: `varstock_amort` must finish before
+//! `stock_amort`, which in turn depends on column 0 of earlier rows. This
+//! module re-implements those stages so the independent work *within* a
+//! stage runs on a rayon thread pool, while keeping stage-to-stage ordering
+//! (and therefore results) identical to the serial path in [`crate::stock`]
+//! and [`crate::flux`].
+//!
+//! Gated behind the `rayon` feature; builds without it compile none of
+//! this module and run only the serial path.
+#![cfg(feature = "rayon")]
+
+use ndarray::parallel::prelude::*;
+use ndarray::{Array2, Axis};
+
+use crate::error::FtpError;
+use crate::na::NaSentinel;
+use crate::result::{ComputeMethod, FtpResult};
+use crate::utils::broadcast_row;
+
+impl<T: NaSentinel + Send + Sync> FtpResult<T> {
+    /// Parallel counterpart to [`FtpResult::compute`]. Produces bit-identical
+    /// results to the serial path for the same inputs.
+    pub fn compute_parallel(&mut self, method: ComputeMethod) -> Result<(), FtpError> {
+        self.check_dims()?;
+
+        let (nrows, ncols) = self.input_profiles.dim();
+
+        self.stock_amort = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.stock_instal = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.varstock_amort = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.varstock_instal = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.ftp_rate = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.ftp_int = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.market_rate = Some(Array2::<T>::zeros((nrows, ncols)));
+
+        match method {
+            ComputeMethod::Stock => compute_stock_parallel(self, nrows, ncols),
+            ComputeMethod::Flux => compute_flux_parallel(self, nrows, ncols),
+        }
+        self.last_method = Some(method);
+
+        Ok(())
+    }
+}
+
+fn compute_stock_parallel<T: NaSentinel + Send + Sync>(
+    r: &mut FtpResult<T>,
+    nrows: usize,
+    ncols: usize,
+) {
+    // --- Phase 1: stock_amort — rows are independent (no cross-row reads). ---
+    {
+        let outstanding = &r.input_outstanding;
+        let profiles = &r.input_profiles;
+        let sa = r.stock_amort.as_mut().unwrap();
+        sa.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .enumerate()
+            .for_each(|(i, mut row)| {
+                let o = outstanding[[broadcast_row(outstanding, i), 0]];
+                for j in 0..ncols {
+                    row[j] = o * profiles[[i, j]];
+                }
+            });
+    }
+
+    // --- Phase 2: stock_instal — same-row diffs, rows independent. ---
+    {
+        let sa = r.stock_amort.as_ref().unwrap().clone();
+        let si = r.stock_instal.as_mut().unwrap();
+        si.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .enumerate()
+            .for_each(|(i, mut row)| {
+                for j in 1..ncols {
+                    row[j] = sa[[i, j - 1]] - sa[[i, j]];
+                }
+            });
+    }
+
+    // --- Phase 3: varstock_amort — reads the now-complete stock_amort. ---
+    {
+        let sa = r.stock_amort.as_ref().unwrap().clone();
+        let va = r.varstock_amort.as_mut().unwrap();
+        va.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .enumerate()
+            .for_each(|(i, mut row)| {
+                for j in 0..ncols {
+                    row[j] = if i == 0 || j == ncols - 1 {
+                        sa[[i, j]]
+                    } else {
+                        sa[[i, j]] - sa[[i - 1, j + 1]]
+                    };
+                }
+            });
+    }
+
+    // --- Phase 4: varstock_instal — same-row diffs, rows independent. ---
+    {
+        let va = r.varstock_amort.as_ref().unwrap().clone();
+        let vi = r.varstock_instal.as_mut().unwrap();
+        vi.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .enumerate()
+            .for_each(|(i, mut row)| {
+                for j in 1..ncols {
+                    row[j] = va[[i, j - 1]] - va[[i, j]];
+                }
+            });
+    }
+
+    compute_rates_parallel(r, nrows, ncols);
+}
+
+fn compute_flux_parallel<T: NaSentinel + Send + Sync>(
+    r: &mut FtpResult<T>,
+    nrows: usize,
+    ncols: usize,
+) {
+    // Rows must be processed in order: row i's column 0 reads every earlier
+    // row's varstock_amort. Within a row, column 0 must be resolved first
+    // (later columns of the same row read back from it); columns 1..ncols
+    // are then independent of each other and run in parallel.
+    for i in 0..nrows {
+        // Column 0 (sequential: recurses into earlier rows of this same row's front amount).
+        {
+            let outstanding = &r.input_outstanding;
+            let profiles = &r.input_profiles;
+            let value = if i == 0 {
+                profiles[[0, 0]] * outstanding[[broadcast_row(outstanding, 0), 0]]
+            } else {
+                let o = outstanding[[broadcast_row(outstanding, i), 0]];
+                if o.is_na() {
+                    T::na()
+                } else {
+                    let va = r.varstock_amort.as_ref().unwrap();
+                    let mut front_amt = T::zero();
+                    for k in 1..=i {
+                        let v = va[[i - k, k]];
+                        if !v.is_na() {
+                            front_amt = front_amt + v;
+                        }
+                    }
+                    (o - front_amt).max(T::zero())
+                }
+            };
+            r.varstock_amort.as_mut().unwrap()[[i, 0]] = value;
+        }
+        finish_flux_cell(r, i, 0, ncols);
+
+        // Columns 1..ncols: independent given column 0 and earlier rows.
+        let outstanding = &r.input_outstanding;
+        let profiles = r.input_profiles.clone();
+        let va_snapshot = r.varstock_amort.as_ref().unwrap().clone();
+        let col0 = va_snapshot[[i, 0]];
+        let o0 = outstanding[[broadcast_row(outstanding, i), 0]];
+        let results: Vec<T> = (1..ncols)
+            .into_par_iter()
+            .map(|j| {
+                if i == 0 {
+                    profiles[[0, j]] * o0
+                } else if col0.is_na() || profiles[[i, j]].is_na() {
+                    T::na()
+                } else {
+                    col0 * profiles[[i, j]]
+                }
+            })
+            .collect();
+        let va = r.varstock_amort.as_mut().unwrap();
+        for (offset, value) in results.into_iter().enumerate() {
+            va[[i, offset + 1]] = value;
+        }
+        for j in 1..ncols {
+            finish_flux_cell(r, i, j, ncols);
+        }
+    }
+
+    compute_rates_parallel(r, nrows, ncols);
+}
+
+/// Completes varstock_instal / stock_amort / stock_instal for one cell once
+/// `varstock_amort[rownum, colnum]` is available — mirrors
+/// `crate::flux::{flux_varstock_instal, flux_stock_amort, flux_stock_instal}`.
+fn finish_flux_cell<T: NaSentinel + Send + Sync>(
+    r: &mut FtpResult<T>,
+    rownum: usize,
+    colnum: usize,
+    ncols: usize,
+) {
+    use crate::utils::extract_anti_diagonal_rect2;
+    use ndarray::s;
+
+    if colnum > 0 {
+        let va = r.varstock_amort.as_ref().unwrap();
+        let val = va[[rownum, colnum - 1]] - va[[rownum, colnum]];
+        r.varstock_instal.as_mut().unwrap()[[rownum, colnum]] = val;
+    }
+
+    let va = r.varstock_amort.as_ref().unwrap();
+    let value = if rownum == 0 {
+        va[[rownum, colnum]]
+    } else {
+        let slice = va.slice(s![0..rownum + 1, colnum..ncols]);
+        let diag = extract_anti_diagonal_rect2(&slice);
+        diag.into_iter().filter(|v| !v.is_na()).sum::<T>()
+    };
+    r.stock_amort.as_mut().unwrap()[[rownum, colnum]] = value;
+
+    if colnum > 0 {
+        let sa = r.stock_amort.as_ref().unwrap();
+        let val = sa[[rownum, colnum - 1]] - sa[[rownum, colnum]];
+        r.stock_instal.as_mut().unwrap()[[rownum, colnum]] = val;
+    }
+}
+
+/// Parallel counterpart to `crate::stock::compute_rates_from_row`.
+///
+/// `ftp_rate`/`ftp_int` at (row, col) only ever read back *row - 1*'s
+/// `market_rate`/`stock_instal` (already finalised by the time row is
+/// reached), so every column of a row can be computed in parallel — each
+/// column re-sums its own tail independently rather than sharing the
+/// serial path's running accumulators. Floating-point addition isn't
+/// associative, so each column's tail is summed in the *same descending
+/// order* (`(colnum..ncols-1).rev()`) the serial suffix sweep folds it in,
+/// which is what makes this bit-identical to [`crate::stock::compute_rates_from_row`]
+/// rather than merely numerically close (see
+/// `compute_parallel_matches_serial_bit_for_bit` below). The `market_rate`
+/// pass keeps the row's own reverse-column scan — it accumulates over later
+/// columns of the same row — so it stays sequential; each of its cells
+/// re-sums its own tail, so it costs O(ncols²) per row.
+///
+/// Both this and the serial sweep sum every tail in descending order, which
+/// is *not* the order the original O(ncols²) algorithm used — see
+/// `crate::stock::tests::compute_rates_naive_ascending` and its ULP-tolerance test
+/// for how that pre-optimization ordering compares.
+fn compute_rates_parallel<T: NaSentinel + Send + Sync>(
+    r: &mut FtpResult<T>,
+    nrows: usize,
+    ncols: usize,
+) {
+    for i in 0..nrows {
+        let input_rate = r.input_rate.clone();
+        let varstock_instal = r.varstock_instal.as_ref().unwrap().clone();
+        let stock_instal = r.stock_instal.as_ref().unwrap().clone();
+        let market_rate_prev = r.market_rate.as_ref().unwrap().clone();
+
+        let rate_int: Vec<(T, T)> = (0..ncols.saturating_sub(1))
+            .into_par_iter()
+            .map(|colnum| {
+                let rate = if i == 0 {
+                    let mut num = T::zero();
+                    let mut denum = T::zero();
+                    for k in (colnum..ncols - 1).rev() {
+                        num = num + varstock_instal[[0, k + 1]] * input_rate[[broadcast_row(&input_rate, 0), k]];
+                        denum = denum + varstock_instal[[0, k + 1]];
+                    }
+                    if denum != T::zero() {
+                        num / denum
+                    } else {
+                        T::zero()
+                    }
+                } else {
+                    let mut num1 = T::zero();
+                    let mut num2 = T::zero();
+                    let mut denum1 = T::zero();
+                    let mut denum2 = T::zero();
+                    for k in (colnum..ncols - 1).rev() {
+                        num1 = num1 + varstock_instal[[i, k + 1]] * input_rate[[broadcast_row(&input_rate, i), k]];
+                        denum1 = denum1 + varstock_instal[[i, k + 1]];
+                        if k > colnum {
+                            num2 = num2 + stock_instal[[i - 1, k + 1]] * market_rate_prev[[i - 1, k + 1]];
+                            denum2 = denum2 + stock_instal[[i - 1, k + 1]];
+                        }
+                    }
+                    let denum = denum1 + denum2;
+                    if denum != T::zero() {
+                        (num1 + num2) / denum
+                    } else {
+                        T::zero()
+                    }
+                };
+
+                let twelve = T::from(12.0).unwrap();
+                let int = if i == 0 {
+                    let mut num = T::zero();
+                    for k in (colnum..ncols - 1).rev() {
+                        num = num + varstock_instal[[0, k + 1]] * input_rate[[broadcast_row(&input_rate, 0), k]];
+                    }
+                    num / twelve
+                } else {
+                    let mut num1 = T::zero();
+                    let mut num2 = T::zero();
+                    for k in (colnum..ncols - 1).rev() {
+                        num1 = num1 + varstock_instal[[i, k + 1]] * input_rate[[broadcast_row(&input_rate, i), k]];
+                        if k > colnum {
+                            num2 = num2 + stock_instal[[i - 1, k + 1]] * market_rate_prev[[i - 1, k + 1]];
+                        }
+                    }
+                    (num1 + num2) / twelve
+                };
+
+                (rate, int)
+            })
+            .collect();
+
+        for (colnum, (rate, int)) in rate_int.into_iter().enumerate() {
+            r.ftp_rate.as_mut().unwrap()[[i, colnum]] = rate;
+            r.ftp_int.as_mut().unwrap()[[i, colnum]] = int;
+        }
+
+        // market_rate: sequential reverse-column scan within this row only.
+        for j in (1..ncols).rev() {
+            let input_rate = &r.input_rate;
+            let stock_instal = r.stock_instal.as_ref().unwrap();
+            let ftp_rate_mat = r.ftp_rate.as_ref().unwrap();
+
+            let value = if j == ncols - 1 {
+                input_rate[[broadcast_row(input_rate, i), j - 1]]
+            } else {
+                let a = ftp_rate_mat[[i, j - 1]];
+                let mut b = T::zero();
+                let mut c = T::zero();
+                let d = stock_instal[[i, j]];
+                for k in (j..ncols).rev() {
+                    b = b + stock_instal[[i, k]];
+                }
+                for k in (j + 1..ncols).rev() {
+                    c = c + stock_instal[[i, k]] * r.market_rate.as_ref().unwrap()[[i, k]];
+                }
+                if d != T::zero() {
+                    ((a * b) - c) / d
+                } else {
+                    T::zero()
+                }
+            };
+            r.market_rate.as_mut().unwrap()[[i, j]] = value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tiny deterministic PRNG (xorshift32) so this regression test doesn't
+    /// need an external `rand` dependency.
+    fn xorshift32(state: &mut u32) -> f64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 17;
+        *state ^= *state << 5;
+        (*state as f64) / (u32::MAX as f64)
+    }
+
+    fn compute_parallel_matches_serial_for(method: ComputeMethod, nrows: usize, ncols: usize, seed: &mut u32) {
+        let outstanding =
+            Array2::from_shape_fn((nrows, 1), |_| 100.0 + xorshift32(seed) * 900.0);
+        let profiles = Array2::from_shape_fn((nrows, ncols), |_| xorshift32(seed));
+        let rates = Array2::from_shape_fn((nrows, ncols - 1), |_| 0.005 + xorshift32(seed) * 0.05);
+
+        let mut serial = FtpResult::new(outstanding.clone(), profiles.clone(), rates.clone());
+        serial.compute(method).unwrap();
+
+        let mut parallel = FtpResult::new(outstanding, profiles, rates);
+        parallel.compute_parallel(method).unwrap();
+
+        assert_eq!(serial.stock_amort(), parallel.stock_amort());
+        assert_eq!(serial.ftp_rate(), parallel.ftp_rate());
+        assert_eq!(serial.ftp_int(), parallel.ftp_int());
+        assert_eq!(serial.market_rate(), parallel.market_rate());
+    }
+
+    #[test]
+    fn compute_parallel_matches_serial_bit_for_bit() {
+        let mut seed = 0x5eed_1234u32;
+        for trial in 0..5 {
+            let nrows = 2 + trial % 3;
+            let ncols = 3 + trial;
+            compute_parallel_matches_serial_for(ComputeMethod::Stock, nrows, ncols, &mut seed);
+            compute_parallel_matches_serial_for(ComputeMethod::Flux, nrows, ncols, &mut seed);
+        }
+    }
+}
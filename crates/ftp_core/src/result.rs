@@ -2,10 +2,14 @@ use ndarray::Array2;
 
 use crate::error::FtpError;
 use crate::flux;
+use crate::na::NaSentinel;
+use crate::numeric::FtpFloat;
 use crate::stock;
+use crate::utils::broadcast_row;
 
 /// Method used for FTP computation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ComputeMethod {
     Stock,
     Flux,
@@ -13,6 +17,10 @@ pub enum ComputeMethod {
 
 /// Main structure holding all FTP calculation inputs and outputs.
 ///
+/// Generic over the float precision `T` (see [`FtpFloat`] — typically `f32`
+/// or `f64`). Defaults to `f64` so existing callers that write `FtpResult`
+/// without a type parameter keep compiling unchanged.
+///
 /// # Examples
 ///
 /// ```
@@ -27,28 +35,33 @@ pub enum ComputeMethod {
 /// result.compute(ComputeMethod::Stock).unwrap();
 /// assert!(result.stock_amort().is_some());
 /// ```
-pub struct FtpResult {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FtpResult<T: FtpFloat = f64> {
     // Inputs
-    pub(crate) input_outstanding: Array2<f64>,
-    pub(crate) input_profiles: Array2<f64>,
-    pub(crate) input_rate: Array2<f64>,
+    pub(crate) input_outstanding: Array2<T>,
+    pub(crate) input_profiles: Array2<T>,
+    pub(crate) input_rate: Array2<T>,
 
     // Outputs
-    pub(crate) stock_amort: Option<Array2<f64>>,
-    pub(crate) stock_instal: Option<Array2<f64>>,
-    pub(crate) varstock_amort: Option<Array2<f64>>,
-    pub(crate) varstock_instal: Option<Array2<f64>>,
-    pub(crate) ftp_rate: Option<Array2<f64>>,
-    pub(crate) ftp_int: Option<Array2<f64>>,
-    pub(crate) market_rate: Option<Array2<f64>>,
+    pub(crate) stock_amort: Option<Array2<T>>,
+    pub(crate) stock_instal: Option<Array2<T>>,
+    pub(crate) varstock_amort: Option<Array2<T>>,
+    pub(crate) varstock_instal: Option<Array2<T>>,
+    pub(crate) ftp_rate: Option<Array2<T>>,
+    pub(crate) ftp_int: Option<Array2<T>>,
+    pub(crate) market_rate: Option<Array2<T>>,
+
+    // The method `compute()` last ran with, so `recompute_from_row` knows
+    // which row-level routine to re-run.
+    pub(crate) last_method: Option<ComputeMethod>,
 }
 
-impl FtpResult {
+impl<T: FtpFloat> FtpResult<T> {
     /// Creates a new `FtpResult` with the given input matrices.
     pub fn new(
-        input_outstanding: Array2<f64>,
-        input_profiles: Array2<f64>,
-        input_rate: Array2<f64>,
+        input_outstanding: Array2<T>,
+        input_profiles: Array2<T>,
+        input_rate: Array2<T>,
     ) -> Self {
         Self {
             input_outstanding,
@@ -61,19 +74,27 @@ impl FtpResult {
             ftp_rate: None,
             ftp_int: None,
             market_rate: None,
+            last_method: None,
         }
     }
 
     /// Validates that input matrix dimensions are consistent.
-    fn check_dims(&self) -> Result<(), FtpError> {
+    ///
+    /// `input_outstanding`/`input_rate` may each have either `nrows_profiles`
+    /// rows, or exactly 1 — a single shared outstanding balance / rate curve
+    /// broadcast across every cohort (see [`Self::outstanding_at`]/
+    /// [`Self::rate_at`]).
+    pub(crate) fn check_dims(&self) -> Result<(), FtpError> {
         let (nrows_outs, ncols_outs) = self.input_outstanding.dim();
         let (nrows_profiles, _ncols_profiles) = self.input_profiles.dim();
         let (nrows_rate, ncols_rate) = self.input_rate.dim();
 
-        if nrows_outs != nrows_profiles || nrows_outs != nrows_rate {
+        let outs_ok = nrows_outs == 1 || nrows_outs == nrows_profiles;
+        let rate_ok = nrows_rate == 1 || nrows_rate == nrows_profiles;
+        if !outs_ok || !rate_ok {
             return Err(FtpError::DimensionMismatch {
-                expected: (nrows_outs, 0),
-                got: (nrows_profiles, nrows_rate),
+                expected: (nrows_profiles, 0),
+                got: (nrows_outs, nrows_rate),
             });
         }
         if ncols_outs != 1 {
@@ -89,68 +110,188 @@ impl FtpResult {
         Ok(())
     }
 
-    /// Runs the FTP computation using the specified method.
-    pub fn compute(&mut self, method: ComputeMethod) -> Result<(), FtpError> {
-        self.check_dims()?;
-
-        let (nrows, ncols) = self.input_profiles.dim();
-
-        // Initialize output arrays
-        self.stock_amort = Some(Array2::<f64>::zeros((nrows, ncols)));
-        self.stock_instal = Some(Array2::<f64>::zeros((nrows, ncols)));
-        self.varstock_amort = Some(Array2::<f64>::zeros((nrows, ncols)));
-        self.varstock_instal = Some(Array2::<f64>::zeros((nrows, ncols)));
-        self.ftp_rate = Some(Array2::<f64>::zeros((nrows, ncols)));
-        self.ftp_int = Some(Array2::<f64>::zeros((nrows, ncols)));
-        self.market_rate = Some(Array2::<f64>::zeros((nrows, ncols)));
-
-        match method {
-            ComputeMethod::Stock => stock::compute_stock(self, nrows, ncols),
-            ComputeMethod::Flux => flux::compute_flux(self, nrows, ncols),
-        }
-
-        Ok(())
-    }
-
     // --- Getters ---
 
-    pub fn input_outstanding(&self) -> &Array2<f64> {
+    pub fn input_outstanding(&self) -> &Array2<T> {
         &self.input_outstanding
     }
 
-    pub fn input_profiles(&self) -> &Array2<f64> {
+    pub fn input_profiles(&self) -> &Array2<T> {
         &self.input_profiles
     }
 
-    pub fn input_rate(&self) -> &Array2<f64> {
+    pub fn input_rate(&self) -> &Array2<T> {
         &self.input_rate
     }
 
-    pub fn stock_amort(&self) -> Option<&Array2<f64>> {
+    /// Outstanding balance for cohort row `i`, broadcasting row 0 if
+    /// `input_outstanding` holds a single shared balance for every cohort.
+    pub(crate) fn outstanding_at(&self, i: usize) -> T {
+        self.input_outstanding[[broadcast_row(&self.input_outstanding, i), 0]]
+    }
+
+    /// Rate at `(i, j)`, broadcasting row 0 if `input_rate` holds a single
+    /// shared rate curve for every cohort.
+    pub(crate) fn rate_at(&self, i: usize, j: usize) -> T {
+        self.input_rate[[broadcast_row(&self.input_rate, i), j]]
+    }
+
+    pub fn stock_amort(&self) -> Option<&Array2<T>> {
         self.stock_amort.as_ref()
     }
 
-    pub fn stock_instal(&self) -> Option<&Array2<f64>> {
+    pub fn stock_instal(&self) -> Option<&Array2<T>> {
         self.stock_instal.as_ref()
     }
 
-    pub fn varstock_amort(&self) -> Option<&Array2<f64>> {
+    pub fn varstock_amort(&self) -> Option<&Array2<T>> {
         self.varstock_amort.as_ref()
     }
 
-    pub fn varstock_instal(&self) -> Option<&Array2<f64>> {
+    pub fn varstock_instal(&self) -> Option<&Array2<T>> {
         self.varstock_instal.as_ref()
     }
 
-    pub fn ftp_rate(&self) -> Option<&Array2<f64>> {
+    pub fn ftp_rate(&self) -> Option<&Array2<T>> {
         self.ftp_rate.as_ref()
     }
 
-    pub fn ftp_int(&self) -> Option<&Array2<f64>> {
+    pub fn ftp_int(&self) -> Option<&Array2<T>> {
         self.ftp_int.as_ref()
     }
 
-    pub fn market_rate(&self) -> Option<&Array2<f64>> {
+    pub fn market_rate(&self) -> Option<&Array2<T>> {
         self.market_rate.as_ref()
     }
 }
+
+#[cfg(feature = "serde")]
+impl<T> FtpResult<T>
+where
+    T: FtpFloat + serde::Serialize + for<'de> serde::Deserialize<'de>,
+{
+    /// Serializes the full result — inputs plus all seven output matrices,
+    /// if `compute()` has run — to JSON, so it can be cached or shipped
+    /// across a process boundary without recomputation.
+    pub fn to_json(&self) -> Result<String, FtpError> {
+        serde_json::to_string(self).map_err(|e| FtpError::SerdeError(e.to_string()))
+    }
+
+    /// Deserializes a result previously produced by [`Self::to_json`].
+    pub fn from_json(json: &str) -> Result<Self, FtpError> {
+        serde_json::from_str(json).map_err(|e| FtpError::SerdeError(e.to_string()))
+    }
+}
+
+impl<T: NaSentinel> FtpResult<T> {
+    /// Runs the FTP computation using the specified method.
+    ///
+    /// Inputs may contain the NA sentinel (see [`crate::is_na`]) for missing
+    /// outstanding balances or rates. In the flux method it propagates to
+    /// any `varstock_amort` cell that directly depends on it, while the
+    /// column-0 front amount and the anti-diagonal `stock_amort` sums treat
+    /// NA as absent rather than poisoning the whole column. The stock method
+    /// and the `ftp_rate`/`ftp_int`/`market_rate` pass do not check for NA;
+    /// there it enters ordinary NaN arithmetic.
+    pub fn compute(&mut self, method: ComputeMethod) -> Result<(), FtpError> {
+        self.check_dims()?;
+
+        let (nrows, ncols) = self.input_profiles.dim();
+
+        // Initialize output arrays
+        self.stock_amort = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.stock_instal = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.varstock_amort = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.varstock_instal = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.ftp_rate = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.ftp_int = Some(Array2::<T>::zeros((nrows, ncols)));
+        self.market_rate = Some(Array2::<T>::zeros((nrows, ncols)));
+
+        match method {
+            ComputeMethod::Stock => stock::compute_stock(self, nrows, ncols),
+            ComputeMethod::Flux => flux::compute_flux(self, nrows, ncols),
+        }
+        self.last_method = Some(method);
+
+        Ok(())
+    }
+
+    /// Recomputes rows `row..nrows` after an edit to cohort `row`'s
+    /// `input_outstanding`/`input_rate`/`input_profiles`, leaving rows
+    /// `0..row` untouched.
+    ///
+    /// `stock_amort`/`stock_instal`/`varstock_*` and `ftp_rate`/`ftp_int`/
+    /// `market_rate` for row `i` only ever read row `i-1` (or, for the flux
+    /// method's column-0 front amount and anti-diagonal `stock_amort` sums,
+    /// earlier rows only) — never a later
+    /// one — so editing row `row` invalidates exactly the suffix `row..nrows`
+    /// and nothing before it. Errors with [`FtpError::NotComputed`] if
+    /// `compute()` was never run.
+    pub fn recompute_from_row(&mut self, row: usize) -> Result<(), FtpError> {
+        let method = self.last_method.ok_or(FtpError::NotComputed)?;
+        if self.stock_amort.is_none() {
+            return Err(FtpError::NotComputed);
+        }
+
+        let (nrows, ncols) = self.input_profiles.dim();
+        if row >= nrows {
+            return Err(FtpError::RowOutOfBounds { row, nrows });
+        }
+
+        match method {
+            ComputeMethod::Stock => stock::compute_stock_from_row(self, nrows, ncols, row),
+            ComputeMethod::Flux => flux::compute_flux_from_row(self, nrows, ncols, row),
+        }
+
+        Ok(())
+    }
+
+    /// Replaces every NA cell (inputs and, if present, computed outputs) with `value`.
+    pub fn fill_na(&mut self, value: T) {
+        for m in [
+            Some(&mut self.input_outstanding),
+            Some(&mut self.input_profiles),
+            Some(&mut self.input_rate),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            m.mapv_inplace(|x| if x.is_na() { value } else { x });
+        }
+        for m in [
+            self.stock_amort.as_mut(),
+            self.stock_instal.as_mut(),
+            self.varstock_amort.as_mut(),
+            self.varstock_instal.as_mut(),
+            self.ftp_rate.as_mut(),
+            self.ftp_int.as_mut(),
+            self.market_rate.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            m.mapv_inplace(|x| if x.is_na() { value } else { x });
+        }
+    }
+
+    /// Counts NA cells across inputs and (if computed) outputs.
+    pub fn count_na(&self) -> usize {
+        let count = |m: &Array2<T>| m.iter().filter(|x| x.is_na()).count();
+        let mut total = count(&self.input_outstanding) + count(&self.input_profiles) + count(&self.input_rate);
+        for m in [
+            self.stock_amort.as_ref(),
+            self.stock_instal.as_ref(),
+            self.varstock_amort.as_ref(),
+            self.varstock_instal.as_ref(),
+            self.ftp_rate.as_ref(),
+            self.ftp_int.as_ref(),
+            self.market_rate.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            total += count(m);
+        }
+        total
+    }
+}
@@ -0,0 +1,125 @@
+//! Flux-method routines genericized over the [`FtpMatrix`] trait, so the
+//! same algorithm as `crate::flux` runs unmodified on the heap
+//! `ndarray::Array2` backend or the stack [`crate::matrix::Matrix`] backend.
+//!
+//! Intended for small, compile-time-sized grids (e.g. 12×12 monthly
+//! buckets) where `Matrix` avoids a heap allocation per run.
+
+use crate::matrix::FtpMatrix;
+use crate::na::NaSentinel;
+
+/// Flux method: new product (varstock_amort). See `crate::flux::flux_stock_var`.
+pub fn flux_stock_var<T, Outstanding, Profiles, Va>(
+    outstanding: &Outstanding,
+    profiles: &Profiles,
+    va: &mut Va,
+    rownum: usize,
+    colnum: usize,
+) where
+    T: NaSentinel,
+    Outstanding: FtpMatrix<T>,
+    Profiles: FtpMatrix<T>,
+    Va: FtpMatrix<T>,
+{
+    let value = if rownum == 0 {
+        let o = outstanding.get(outstanding.broadcast_row(rownum), 0);
+        let p = profiles.get(rownum, colnum);
+        if o.is_na() || p.is_na() {
+            T::na()
+        } else {
+            p * o
+        }
+    } else if colnum == 0 {
+        let o = outstanding.get(outstanding.broadcast_row(rownum), 0);
+        if o.is_na() {
+            T::na()
+        } else {
+            let mut front_amt = T::zero();
+            for k in 1..=rownum {
+                let v = va.get(rownum - k, k);
+                if !v.is_na() {
+                    front_amt = front_amt + v;
+                }
+            }
+            (o - front_amt).max(T::zero())
+        }
+    } else {
+        let v = va.get(rownum, 0);
+        let p = profiles.get(rownum, colnum);
+        if v.is_na() || p.is_na() {
+            T::na()
+        } else {
+            v * p
+        }
+    };
+    va.set(rownum, colnum, value);
+}
+
+/// See `crate::flux::flux_varstock_instal`.
+pub fn flux_varstock_instal<T, Va, Vi>(va: &Va, vi: &mut Vi, rownum: usize, colnum: usize)
+where
+    T: NaSentinel,
+    Va: FtpMatrix<T>,
+    Vi: FtpMatrix<T>,
+{
+    if colnum > 0 {
+        let val = va.get(rownum, colnum - 1) - va.get(rownum, colnum);
+        vi.set(rownum, colnum, val);
+    }
+}
+
+/// Flux method: stock_amort via anti-diagonal sums, without allocating a
+/// `Vec` for the diagonal. See `crate::flux::flux_stock_amort`.
+pub fn flux_stock_amort<T, Va, Sa>(va: &Va, sa: &mut Sa, rownum: usize, colnum: usize)
+where
+    T: NaSentinel,
+    Va: FtpMatrix<T>,
+    Sa: FtpMatrix<T>,
+{
+    let value = if rownum == 0 {
+        va.get(0, colnum)
+    } else {
+        va.anti_diagonal_sum(rownum, colnum, |v| v.is_na())
+    };
+    sa.set(rownum, colnum, value);
+}
+
+/// See `crate::flux::flux_stock_instal`.
+pub fn flux_stock_instal<T, Sa, Si>(sa: &Sa, si: &mut Si, rownum: usize, colnum: usize)
+where
+    T: NaSentinel,
+    Sa: FtpMatrix<T>,
+    Si: FtpMatrix<T>,
+{
+    if colnum > 0 {
+        let val = sa.get(rownum, colnum - 1) - sa.get(rownum, colnum);
+        si.set(rownum, colnum, val);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn small_flux_stock_var_matches_array_backend_first_row() {
+        let outstanding = Matrix::<f64, 1, 1>::default();
+        let mut outstanding = outstanding;
+        outstanding.set(0, 0, 800.0);
+
+        let mut profiles = Matrix::<f64, 1, 3>::default();
+        profiles.set(0, 0, 1.0);
+        profiles.set(0, 1, 0.6);
+        profiles.set(0, 2, 0.3);
+
+        let mut va = Matrix::<f64, 1, 3>::default();
+        flux_stock_var(&outstanding, &profiles, &mut va, 0, 0);
+        flux_stock_var(&outstanding, &profiles, &mut va, 0, 1);
+        flux_stock_var(&outstanding, &profiles, &mut va, 0, 2);
+
+        assert_eq!(va.get(0, 0), 800.0);
+        assert_eq!(va.get(0, 1), 480.0);
+        assert_eq!(va.get(0, 2), 240.0);
+    }
+}
@@ -1,15 +1,30 @@
+use crate::numeric::FtpFloat;
 use crate::result::FtpResult;
 
 /// Executes the **stock** method computation on an `FtpResult`.
 ///
 /// All output matrices must already be initialised (via `compute()`).
-pub(crate) fn compute_stock(r: &mut FtpResult, nrows: usize, ncols: usize) {
+pub(crate) fn compute_stock<T: FtpFloat>(r: &mut FtpResult<T>, nrows: usize, ncols: usize) {
+    compute_stock_from_row(r, nrows, ncols, 0);
+}
+
+/// Same as [`compute_stock`], but only (re)computes rows `from_row..nrows`,
+/// leaving rows `0..from_row` untouched. Every phase below reads at most
+/// row `i-1`, so this is safe as long as rows `0..from_row` are already
+/// up to date — see [`FtpResult::recompute_from_row`].
+pub(crate) fn compute_stock_from_row<T: FtpFloat>(
+    r: &mut FtpResult<T>,
+    nrows: usize,
+    ncols: usize,
+    from_row: usize,
+) {
     // --- Phase 1: stock_amort (vectorisable) ---
     // stock_amort[i,j] = outstanding[i,0] * profiles[i,j]
     {
+        let outstanding: Vec<T> = (from_row..nrows).map(|i| r.outstanding_at(i)).collect();
         let sa = r.stock_amort.as_mut().unwrap();
-        for i in 0..nrows {
-            let o = r.input_outstanding[[i, 0]];
+        for (offset, i) in (from_row..nrows).enumerate() {
+            let o = outstanding[offset];
             for j in 0..ncols {
                 sa[[i, j]] = o * r.input_profiles[[i, j]];
             }
@@ -22,7 +37,7 @@ pub(crate) fn compute_stock(r: &mut FtpResult, nrows: usize, ncols: usize) {
     {
         let sa = r.stock_amort.as_ref().unwrap();
         let si = r.stock_instal.as_mut().unwrap();
-        for i in 0..nrows {
+        for i in from_row..nrows {
             for j in 1..ncols {
                 si[[i, j]] = sa[[i, j - 1]] - sa[[i, j]];
             }
@@ -35,7 +50,7 @@ pub(crate) fn compute_stock(r: &mut FtpResult, nrows: usize, ncols: usize) {
     {
         let sa = r.stock_amort.as_ref().unwrap();
         let va = r.varstock_amort.as_mut().unwrap();
-        for i in 0..nrows {
+        for i in from_row..nrows {
             for j in 0..ncols {
                 if i == 0 || j == ncols - 1 {
                     va[[i, j]] = sa[[i, j]];
@@ -52,7 +67,7 @@ pub(crate) fn compute_stock(r: &mut FtpResult, nrows: usize, ncols: usize) {
     {
         let va = r.varstock_amort.as_ref().unwrap();
         let vi = r.varstock_instal.as_mut().unwrap();
-        for i in 0..nrows {
+        for i in from_row..nrows {
             for j in 1..ncols {
                 vi[[i, j]] = va[[i, j - 1]] - va[[i, j]];
             }
@@ -60,125 +75,89 @@ pub(crate) fn compute_stock(r: &mut FtpResult, nrows: usize, ncols: usize) {
     }
 
     // --- Phase 5: ftp_rate, ftp_int, market_rate (reverse-column, row-by-row) ---
-    compute_rates(r, nrows, ncols);
-}
-
-/// Computes ftp_rate, ftp_int, and market_rate (shared by stock and flux).
-pub(crate) fn compute_rates(r: &mut FtpResult, nrows: usize, ncols: usize) {
-    for i in 0..nrows {
-        for j in (0..ncols).rev() {
-            if j > 0 {
-                compute_ftp_rate(r, i, j - 1, ncols);
-                compute_ftp_int(r, i, j - 1, ncols);
-                compute_market_rate(r, i, j, ncols);
-            }
-        }
-    }
+    compute_rates_from_row(r, nrows, ncols, from_row);
 }
 
-/// FTP rate for cell (rownum, colnum).
+/// Computes ftp_rate, ftp_int, and market_rate (shared by stock and flux),
+/// for rows `from_row..nrows` — row `i`'s accumulators only ever read row
+/// `i-1`'s finished `market_rate`/`stock_instal`, so this is safe as long as
+/// rows `0..from_row` are already up to date.
 ///
-/// Row 0:  weighted average of varstock_instal × input_rate
-/// Row >0: weighted average of (varstock_instal × input_rate) + (stock_instal × market_rate)
-fn compute_ftp_rate(r: &mut FtpResult, rownum: usize, colnum: usize, ncols: usize) {
-    let input_rate = &r.input_rate;
-    let varstock_instal = r.varstock_instal.as_ref().unwrap();
-    let stock_instal = r.stock_instal.as_ref().unwrap();
-    let market_rate_mat = r.market_rate.as_ref().unwrap();
-
-    let value = if rownum == 0 {
-        let mut num = 0.0;
-        let mut denum = 0.0;
-        for k in colnum..ncols - 1 {
-            num += varstock_instal[[0, k + 1]] * input_rate[[0, k]];
-            denum += varstock_instal[[0, k + 1]];
-        }
-        if denum != 0.0 {
-            num / denum
-        } else {
-            0.0
-        }
-    } else {
-        let mut num1 = 0.0;
-        let mut num2 = 0.0;
-        let mut denum1 = 0.0;
-        let mut denum2 = 0.0;
-        for k in colnum..ncols - 1 {
-            num1 += varstock_instal[[rownum, k + 1]] * input_rate[[rownum, k]];
-            denum1 += varstock_instal[[rownum, k + 1]];
-            if k > colnum {
-                num2 += stock_instal[[rownum - 1, k + 1]] * market_rate_mat[[rownum - 1, k + 1]];
-                denum2 += stock_instal[[rownum - 1, k + 1]];
-            }
-        }
-        let denum = denum1 + denum2;
-        if denum != 0.0 {
-            (num1 + num2) / denum
-        } else {
-            0.0
-        }
-    };
+/// Each row is an independent reverse-column scan (`for j in
+/// (0..ncols).rev()`), so rather than re-summing a tail of the row for
+/// every cell — the naive approach, O(ncols) per cell and O(ncols²) per
+/// row — this keeps running suffix accumulators that fold in exactly one
+/// new term per column, dropping the whole pass to O(ncols) per row:
+///
+/// - `num_v`/`denum_v`: row `i`'s own weighted average
+///   `Σ varstock_instal[i,k+1] * input_rate[i,k]` over `k >= colnum`.
+/// - `num_s`/`denum_s`: the row `i-1` weighted average
+///   `Σ stock_instal[i-1,k+1] * market_rate[i-1,k+1]` over `k > colnum`
+///   (folded in *after* the cell that doesn't yet need it, since the
+///   `k > colnum` range lags one column behind `num_v`/`denum_v`'s
+///   `k >= colnum`).
+/// - `b`/`c`: market_rate's own `Σ_{k>=colnum} stock_instal[i,k]` and
+///   `Σ_{k>colnum} stock_instal[i,k] * market_rate[i,k]`, folded in the
+///   same lagged way using this row's own just-computed market_rate.
+pub(crate) fn compute_rates_from_row<T: FtpFloat>(
+    r: &mut FtpResult<T>,
+    nrows: usize,
+    ncols: usize,
+    from_row: usize,
+) {
+    let twelve = T::from(12.0).unwrap();
 
-    r.ftp_rate.as_mut().unwrap()[[rownum, colnum]] = value;
-}
+    for i in from_row..nrows {
+        let mut num_v = T::zero();
+        let mut denum_v = T::zero();
+        let mut num_s = T::zero();
+        let mut denum_s = T::zero();
+        let mut b = T::zero();
+        let mut c = T::zero();
 
-/// FTP interest for cell (rownum, colnum).
-fn compute_ftp_int(r: &mut FtpResult, rownum: usize, colnum: usize, ncols: usize) {
-    let input_rate = &r.input_rate;
-    let varstock_instal = r.varstock_instal.as_ref().unwrap();
-    let stock_instal = r.stock_instal.as_ref().unwrap();
-    let market_rate_mat = r.market_rate.as_ref().unwrap();
-
-    let value = if rownum == 0 {
-        let mut num = 0.0;
-        for k in colnum..ncols - 1 {
-            num += varstock_instal[[0, k + 1]] * input_rate[[0, k]];
-        }
-        num / 12.0
-    } else {
-        let mut num1 = 0.0;
-        let mut num2 = 0.0;
-        for k in colnum..ncols - 1 {
-            num1 += varstock_instal[[rownum, k + 1]] * input_rate[[rownum, k]];
-            if k > colnum {
-                num2 += stock_instal[[rownum - 1, k + 1]] * market_rate_mat[[rownum - 1, k + 1]];
+        for j in (0..ncols).rev() {
+            if j == 0 {
+                continue;
             }
-        }
-        (num1 + num2) / 12.0
-    };
+            let colnum = j - 1;
 
-    r.ftp_int.as_mut().unwrap()[[rownum, colnum]] = value;
-}
+            // --- ftp_rate / ftp_int at (i, colnum) ---
+            let vi = r.varstock_instal.as_ref().unwrap()[[i, colnum + 1]];
+            num_v = num_v + vi * r.rate_at(i, colnum);
+            denum_v = denum_v + vi;
 
-/// Market rate for cell (rownum, colnum).
-fn compute_market_rate(r: &mut FtpResult, rownum: usize, colnum: usize, ncols: usize) {
-    let input_rate = &r.input_rate;
-    let stock_instal = r.stock_instal.as_ref().unwrap();
-    let ftp_rate_mat = r.ftp_rate.as_ref().unwrap();
-
-    let value = if colnum == ncols - 1 {
-        input_rate[[rownum, colnum - 1]]
-    } else {
-        let a = ftp_rate_mat[[rownum, colnum - 1]];
-        let mut b = 0.0;
-        let mut c = 0.0;
-        let d = stock_instal[[rownum, colnum]];
-
-        for k in colnum..ncols {
-            b += stock_instal[[rownum, k]];
-        }
-        for k in colnum + 1..ncols {
-            c += stock_instal[[rownum, k]] * r.market_rate.as_ref().unwrap()[[rownum, k]];
-        }
+            let denum = denum_v + denum_s;
+            r.ftp_rate.as_mut().unwrap()[[i, colnum]] = if denum != T::zero() {
+                (num_v + num_s) / denum
+            } else {
+                T::zero()
+            };
+            r.ftp_int.as_mut().unwrap()[[i, colnum]] = (num_v + num_s) / twelve;
 
-        if d != 0.0 {
-            ((a * b) - c) / d
-        } else {
-            0.0
-        }
-    };
+            if i > 0 {
+                let si_prev = r.stock_instal.as_ref().unwrap()[[i - 1, colnum + 1]];
+                let mr_prev = r.market_rate.as_ref().unwrap()[[i - 1, colnum + 1]];
+                num_s = num_s + si_prev * mr_prev;
+                denum_s = denum_s + si_prev;
+            }
 
-    r.market_rate.as_mut().unwrap()[[rownum, colnum]] = value;
+            // --- market_rate at (i, j) ---
+            let si = r.stock_instal.as_ref().unwrap()[[i, j]];
+            b = b + si;
+            let value = if j == ncols - 1 {
+                r.rate_at(i, j - 1)
+            } else {
+                let a = r.ftp_rate.as_ref().unwrap()[[i, j - 1]];
+                if si != T::zero() {
+                    (a * b - c) / si
+                } else {
+                    T::zero()
+                }
+            };
+            r.market_rate.as_mut().unwrap()[[i, j]] = value;
+            c = c + si * value;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -228,4 +207,397 @@ mod tests {
         assert_eq!(va[[0, 0]], sa[[0, 0]]);
         assert_eq!(va[[0, 1]], sa[[0, 1]]);
     }
+
+    #[test]
+    fn test_stock_amort_f32_precision() {
+        let mut r = FtpResult::<f32>::new(
+            array![[1000.0f32]],
+            array![[1.0, 0.5, 0.2]],
+            array![[0.01, 0.02]],
+        );
+        r.compute(ComputeMethod::Stock).unwrap();
+        let sa = r.stock_amort().unwrap();
+        assert_eq!(sa[[0, 0]], 1000.0f32);
+        assert_eq!(sa[[0, 1]], 500.0f32);
+    }
+
+    #[test]
+    fn test_recompute_from_row_matches_full_recompute_after_edit() {
+        let outstanding = array![[1000.0], [800.0], [900.0]];
+        let profiles = array![[1.0, 0.5, 0.2], [1.0, 0.6, 0.3], [1.0, 0.7, 0.4]];
+        let rate = array![[0.01, 0.02], [0.011, 0.021], [0.012, 0.022]];
+
+        let mut r = FtpResult::new(outstanding.clone(), profiles.clone(), rate.clone());
+        r.compute(ComputeMethod::Stock).unwrap();
+
+        // Edit row 1's outstanding balance, then recompute only rows 1..nrows.
+        r.input_outstanding[[1, 0]] = 750.0;
+        r.recompute_from_row(1).unwrap();
+
+        // A from-scratch compute on the same edited inputs must agree exactly.
+        let mut expected = FtpResult::new(
+            array![[1000.0], [750.0], [900.0]],
+            profiles,
+            rate,
+        );
+        expected.compute(ComputeMethod::Stock).unwrap();
+
+        assert_eq!(r.stock_amort().unwrap(), expected.stock_amort().unwrap());
+        assert_eq!(r.ftp_rate().unwrap(), expected.ftp_rate().unwrap());
+        assert_eq!(r.market_rate().unwrap(), expected.market_rate().unwrap());
+
+        // Row 0 (before the edited row) is untouched, as expected.
+        assert_eq!(r.stock_amort().unwrap()[[0, 0]], 1000.0);
+    }
+
+    #[test]
+    fn test_recompute_from_row_errors_before_compute() {
+        let mut r = FtpResult::new(
+            array![[1000.0]],
+            array![[1.0, 0.5, 0.2]],
+            array![[0.01, 0.02]],
+        );
+        assert!(matches!(
+            r.recompute_from_row(0),
+            Err(crate::error::FtpError::NotComputed)
+        ));
+    }
+
+    #[test]
+    fn test_single_row_outstanding_and_rate_broadcast_to_every_cohort() {
+        let outstanding = array![[1000.0]];
+        let profiles = array![[1.0, 0.5, 0.2], [1.0, 0.6, 0.3], [1.0, 0.7, 0.4]];
+        let rate = array![[0.01, 0.02]];
+
+        let mut broadcast = FtpResult::new(outstanding.clone(), profiles.clone(), rate.clone());
+        broadcast.compute(ComputeMethod::Stock).unwrap();
+
+        let tiled_outstanding = array![[1000.0], [1000.0], [1000.0]];
+        let tiled_rate = array![[0.01, 0.02], [0.01, 0.02], [0.01, 0.02]];
+        let mut tiled = FtpResult::new(tiled_outstanding, profiles, tiled_rate);
+        tiled.compute(ComputeMethod::Stock).unwrap();
+
+        assert_eq!(broadcast.stock_amort().unwrap(), tiled.stock_amort().unwrap());
+        assert_eq!(broadcast.ftp_rate().unwrap(), tiled.ftp_rate().unwrap());
+        assert_eq!(broadcast.market_rate().unwrap(), tiled.market_rate().unwrap());
+    }
+
+    /// Naive, pre-suffix-sum reimplementation of `compute_rates`, kept only
+    /// to regression-test the O(ncols) version above against.
+    ///
+    /// Each cell re-sums its whole tail from scratch (the O(ncols²)
+    /// behaviour the suffix sweep replaces), but visits that tail in the
+    /// *same descending order* the sweep's running accumulators fold it
+    /// in — floating-point addition isn't associative, so matching the
+    /// fold order (not just the summand set) is what makes this
+    /// bit-for-bit comparable to the optimized version. This proves the
+    /// sweep is *internally consistent* with its own documented fold
+    /// order (and, by construction, with
+    /// [`crate::parallel::compute_rates_parallel`], which sums the same
+    /// tails in the same descending order). It does not by itself prove
+    /// the sweep matches the original ascending-order algorithm it
+    /// replaced — see [`compute_rates_naive_ascending`] and
+    /// `test_compute_rates_matches_ascending_naive_within_ulp_tolerance`
+    /// for that comparison.
+    fn compute_rates_naive<T: FtpFloat>(r: &mut FtpResult<T>, nrows: usize, ncols: usize) {
+        for i in 0..nrows {
+            for j in (0..ncols).rev() {
+                if j == 0 {
+                    continue;
+                }
+                let colnum = j - 1;
+
+                let value = if i == 0 {
+                    let mut num = T::zero();
+                    let mut denum = T::zero();
+                    for k in (colnum..ncols - 1).rev() {
+                        num = num + r.varstock_instal.as_ref().unwrap()[[0, k + 1]]
+                            * r.input_rate[[0, k]];
+                        denum = denum + r.varstock_instal.as_ref().unwrap()[[0, k + 1]];
+                    }
+                    if denum != T::zero() {
+                        num / denum
+                    } else {
+                        T::zero()
+                    }
+                } else {
+                    let mut num1 = T::zero();
+                    let mut num2 = T::zero();
+                    let mut denum1 = T::zero();
+                    let mut denum2 = T::zero();
+                    for k in (colnum..ncols - 1).rev() {
+                        num1 = num1
+                            + r.varstock_instal.as_ref().unwrap()[[i, k + 1]]
+                                * r.input_rate[[i, k]];
+                        denum1 = denum1 + r.varstock_instal.as_ref().unwrap()[[i, k + 1]];
+                        if k > colnum {
+                            num2 = num2
+                                + r.stock_instal.as_ref().unwrap()[[i - 1, k + 1]]
+                                    * r.market_rate.as_ref().unwrap()[[i - 1, k + 1]];
+                            denum2 = denum2 + r.stock_instal.as_ref().unwrap()[[i - 1, k + 1]];
+                        }
+                    }
+                    let denum = denum1 + denum2;
+                    if denum != T::zero() {
+                        (num1 + num2) / denum
+                    } else {
+                        T::zero()
+                    }
+                };
+                r.ftp_rate.as_mut().unwrap()[[i, colnum]] = value;
+
+                let twelve = T::from(12.0).unwrap();
+                let int_value = if i == 0 {
+                    let mut num = T::zero();
+                    for k in (colnum..ncols - 1).rev() {
+                        num = num + r.varstock_instal.as_ref().unwrap()[[0, k + 1]]
+                            * r.input_rate[[0, k]];
+                    }
+                    num / twelve
+                } else {
+                    let mut num1 = T::zero();
+                    let mut num2 = T::zero();
+                    for k in (colnum..ncols - 1).rev() {
+                        num1 = num1
+                            + r.varstock_instal.as_ref().unwrap()[[i, k + 1]]
+                                * r.input_rate[[i, k]];
+                        if k > colnum {
+                            num2 = num2
+                                + r.stock_instal.as_ref().unwrap()[[i - 1, k + 1]]
+                                    * r.market_rate.as_ref().unwrap()[[i - 1, k + 1]];
+                        }
+                    }
+                    (num1 + num2) / twelve
+                };
+                r.ftp_int.as_mut().unwrap()[[i, colnum]] = int_value;
+
+                let market_value = if j == ncols - 1 {
+                    r.input_rate[[i, j - 1]]
+                } else {
+                    let a = r.ftp_rate.as_ref().unwrap()[[i, j - 1]];
+                    let mut b = T::zero();
+                    let mut c = T::zero();
+                    let d = r.stock_instal.as_ref().unwrap()[[i, j]];
+                    for k in (j..ncols).rev() {
+                        b = b + r.stock_instal.as_ref().unwrap()[[i, k]];
+                    }
+                    for k in (j + 1..ncols).rev() {
+                        c = c + r.stock_instal.as_ref().unwrap()[[i, k]]
+                            * r.market_rate.as_ref().unwrap()[[i, k]];
+                    }
+                    if d != T::zero() {
+                        ((a * b) - c) / d
+                    } else {
+                        T::zero()
+                    }
+                };
+                r.market_rate.as_mut().unwrap()[[i, j]] = market_value;
+            }
+        }
+    }
+
+    /// Same algorithm as [`compute_rates_naive`], but walks every tail in
+    /// *ascending* column order — the order the O(ncols²) routine the
+    /// suffix sweep replaced actually used. Floating-point addition isn't
+    /// associative, so this differs from the sweep at the ULP level even
+    /// though it sums the identical set of terms; see
+    /// `test_compute_rates_matches_ascending_naive_within_ulp_tolerance`,
+    /// which checks the two stay within a small documented tolerance of
+    /// each other instead of asserting exact equality.
+    fn compute_rates_naive_ascending<T: FtpFloat>(r: &mut FtpResult<T>, nrows: usize, ncols: usize) {
+        for i in 0..nrows {
+            for j in (0..ncols).rev() {
+                if j == 0 {
+                    continue;
+                }
+                let colnum = j - 1;
+
+                let value = if i == 0 {
+                    let mut num = T::zero();
+                    let mut denum = T::zero();
+                    for k in colnum..ncols - 1 {
+                        num = num + r.varstock_instal.as_ref().unwrap()[[0, k + 1]]
+                            * r.input_rate[[0, k]];
+                        denum = denum + r.varstock_instal.as_ref().unwrap()[[0, k + 1]];
+                    }
+                    if denum != T::zero() {
+                        num / denum
+                    } else {
+                        T::zero()
+                    }
+                } else {
+                    let mut num1 = T::zero();
+                    let mut num2 = T::zero();
+                    let mut denum1 = T::zero();
+                    let mut denum2 = T::zero();
+                    for k in colnum..ncols - 1 {
+                        num1 = num1
+                            + r.varstock_instal.as_ref().unwrap()[[i, k + 1]]
+                                * r.input_rate[[i, k]];
+                        denum1 = denum1 + r.varstock_instal.as_ref().unwrap()[[i, k + 1]];
+                        if k > colnum {
+                            num2 = num2
+                                + r.stock_instal.as_ref().unwrap()[[i - 1, k + 1]]
+                                    * r.market_rate.as_ref().unwrap()[[i - 1, k + 1]];
+                            denum2 = denum2 + r.stock_instal.as_ref().unwrap()[[i - 1, k + 1]];
+                        }
+                    }
+                    let denum = denum1 + denum2;
+                    if denum != T::zero() {
+                        (num1 + num2) / denum
+                    } else {
+                        T::zero()
+                    }
+                };
+                r.ftp_rate.as_mut().unwrap()[[i, colnum]] = value;
+
+                let twelve = T::from(12.0).unwrap();
+                let int_value = if i == 0 {
+                    let mut num = T::zero();
+                    for k in colnum..ncols - 1 {
+                        num = num + r.varstock_instal.as_ref().unwrap()[[0, k + 1]]
+                            * r.input_rate[[0, k]];
+                    }
+                    num / twelve
+                } else {
+                    let mut num1 = T::zero();
+                    let mut num2 = T::zero();
+                    for k in colnum..ncols - 1 {
+                        num1 = num1
+                            + r.varstock_instal.as_ref().unwrap()[[i, k + 1]]
+                                * r.input_rate[[i, k]];
+                        if k > colnum {
+                            num2 = num2
+                                + r.stock_instal.as_ref().unwrap()[[i - 1, k + 1]]
+                                    * r.market_rate.as_ref().unwrap()[[i - 1, k + 1]];
+                        }
+                    }
+                    (num1 + num2) / twelve
+                };
+                r.ftp_int.as_mut().unwrap()[[i, colnum]] = int_value;
+
+                let market_value = if j == ncols - 1 {
+                    r.input_rate[[i, j - 1]]
+                } else {
+                    let a = r.ftp_rate.as_ref().unwrap()[[i, j - 1]];
+                    let mut b = T::zero();
+                    let mut c = T::zero();
+                    let d = r.stock_instal.as_ref().unwrap()[[i, j]];
+                    for k in j..ncols {
+                        b = b + r.stock_instal.as_ref().unwrap()[[i, k]];
+                    }
+                    for k in j + 1..ncols {
+                        c = c + r.stock_instal.as_ref().unwrap()[[i, k]]
+                            * r.market_rate.as_ref().unwrap()[[i, k]];
+                    }
+                    if d != T::zero() {
+                        ((a * b) - c) / d
+                    } else {
+                        T::zero()
+                    }
+                };
+                r.market_rate.as_mut().unwrap()[[i, j]] = market_value;
+            }
+        }
+    }
+
+    /// Number of 1-ULP steps between two `f64`s, via their monotonic
+    /// integer bit-pattern ordering (flipping the sign bit into the rest
+    /// so negative and positive values both sort correctly).
+    fn ulp_distance(a: f64, b: f64) -> u64 {
+        fn key(x: f64) -> i64 {
+            let bits = x.to_bits() as i64;
+            if bits < 0 {
+                i64::MIN - bits
+            } else {
+                bits
+            }
+        }
+        key(a).abs_diff(key(b))
+    }
+
+    /// Tiny deterministic PRNG (xorshift32) so the regression test below
+    /// doesn't need an external `rand` dependency.
+    fn xorshift32(state: &mut u32) -> f64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 17;
+        *state ^= *state << 5;
+        (*state as f64) / (u32::MAX as f64)
+    }
+
+    #[test]
+    fn test_compute_rates_matches_naive_on_random_matrices() {
+        let mut seed = 0x1234_5678u32;
+        for trial in 0..5 {
+            let nrows = 2 + trial % 3;
+            let ncols = 3 + trial;
+
+            let outstanding =
+                ndarray::Array2::from_shape_fn((nrows, 1), |_| 100.0 + xorshift32(&mut seed) * 900.0);
+            let profiles = ndarray::Array2::from_shape_fn((nrows, ncols), |_| {
+                xorshift32(&mut seed)
+            });
+            let rates = ndarray::Array2::from_shape_fn((nrows, ncols - 1), |_| {
+                0.005 + xorshift32(&mut seed) * 0.05
+            });
+
+            let mut fast = FtpResult::new(outstanding.clone(), profiles.clone(), rates.clone());
+            fast.compute(ComputeMethod::Stock).unwrap();
+
+            let mut naive = FtpResult::new(outstanding, profiles, rates);
+            naive.compute(ComputeMethod::Stock).unwrap();
+            compute_rates_naive(&mut naive, nrows, ncols);
+
+            assert_eq!(fast.ftp_rate().unwrap(), naive.ftp_rate().unwrap());
+            assert_eq!(fast.ftp_int().unwrap(), naive.ftp_int().unwrap());
+            assert_eq!(fast.market_rate().unwrap(), naive.market_rate().unwrap());
+        }
+    }
+
+    /// The suffix sweep sums the same tails as the original O(ncols²)
+    /// algorithm but in descending rather than ascending order, so the two
+    /// are expected to diverge at the ULP level (floating-point addition
+    /// isn't associative) even though they compute the same quantity.
+    /// This pins that divergence to a small documented tolerance instead
+    /// of leaving it unverified.
+    #[test]
+    fn test_compute_rates_matches_ascending_naive_within_ulp_tolerance() {
+        // Observed divergence tops out around 13 ULPs across these trial
+        // sizes (the ratios amplify the reordered sums' rounding error);
+        // 256 leaves headroom without being so loose it'd miss a genuine
+        // algorithmic regression, which would show up many orders of
+        // magnitude larger than a rounding-order difference.
+        const MAX_ULPS: u64 = 256;
+        let mut seed = 0x9e37_79b9u32;
+        for trial in 0..5 {
+            let nrows = 2 + trial % 3;
+            let ncols = 3 + trial;
+
+            let outstanding =
+                ndarray::Array2::from_shape_fn((nrows, 1), |_| 100.0 + xorshift32(&mut seed) * 900.0);
+            let profiles = ndarray::Array2::from_shape_fn((nrows, ncols), |_| {
+                xorshift32(&mut seed)
+            });
+            let rates = ndarray::Array2::from_shape_fn((nrows, ncols - 1), |_| {
+                0.005 + xorshift32(&mut seed) * 0.05
+            });
+
+            let mut fast = FtpResult::new(outstanding.clone(), profiles.clone(), rates.clone());
+            fast.compute(ComputeMethod::Stock).unwrap();
+
+            let mut naive = FtpResult::new(outstanding, profiles, rates);
+            naive.compute(ComputeMethod::Stock).unwrap();
+            compute_rates_naive_ascending(&mut naive, nrows, ncols);
+
+            for (a, b) in fast.ftp_rate().unwrap().iter().zip(naive.ftp_rate().unwrap()) {
+                assert!(ulp_distance(*a, *b) <= MAX_ULPS, "ftp_rate diverged: {a} vs {b}");
+            }
+            for (a, b) in fast.ftp_int().unwrap().iter().zip(naive.ftp_int().unwrap()) {
+                assert!(ulp_distance(*a, *b) <= MAX_ULPS, "ftp_int diverged: {a} vs {b}");
+            }
+            for (a, b) in fast.market_rate().unwrap().iter().zip(naive.market_rate().unwrap()) {
+                assert!(ulp_distance(*a, *b) <= MAX_ULPS, "market_rate diverged: {a} vs {b}");
+            }
+        }
+    }
 }
@@ -1,13 +1,28 @@
 use ndarray::prelude::*;
-use ndarray::ArrayBase;
+use ndarray::{Array2, ArrayBase};
 
-pub fn extract_anti_diagonal_rect2<T>(arr: &ArrayBase<T, Ix2>) -> Vec<f64>
+use crate::numeric::FtpFloat;
+
+/// Maps row `i` of a broadcastable input to its actual row in `m`: a
+/// single-row `m` (one rate curve / one outstanding balance shared by every
+/// cohort) always resolves to row 0, rather than requiring a caller to
+/// tile it out to `nrows` first.
+pub(crate) fn broadcast_row<T>(m: &Array2<T>, i: usize) -> usize {
+    if m.dim().0 == 1 {
+        0
+    } else {
+        i
+    }
+}
+
+pub fn extract_anti_diagonal_rect2<S, F>(arr: &ArrayBase<S, Ix2>) -> Vec<F>
 where
-    T: ndarray::Data<Elem = f64>,
+    S: ndarray::Data<Elem = F>,
+    F: FtpFloat,
 {
     let (nrows, ncols) = arr.dim();
-    // Create empty Vec<f64>
-    let mut numbers: Vec<f64> = Vec::new();
+    // Create empty Vec<F>
+    let mut numbers: Vec<F> = Vec::new();
     if nrows < ncols {
         for i in 0..nrows {
             numbers.push(arr[[nrows - i - 1, i]]);